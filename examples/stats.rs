@@ -20,10 +20,8 @@ fn main() {
                     max_forms_in_lemma = forms;
                 }
 
-                let lemma_grammemes = lemma.grammemes.len();
                 for form in &lemma.forms {
-                    let form_grammemes = form.grammemes.len();
-                    let total_grammemes = lemma_grammemes + form_grammemes;
+                    let total_grammemes = lemma.tag(form).grammemes().count();
                     if max_grammemes_in_form < total_grammemes {
                         max_grammemes_in_form = total_grammemes;
                     }
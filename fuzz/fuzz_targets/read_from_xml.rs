@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use opencorpora::{Dict, ParseOptions};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Dict::read_from_xml(data);
+    let mut lenient = ParseOptions::default();
+    lenient.strict = false;
+    let _ = Dict::read_from_xml_with_options(data, &lenient);
+});
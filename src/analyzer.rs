@@ -0,0 +1,63 @@
+use crate::dict::Dict;
+use crate::tag::Tag;
+
+/// Вариант морфологического разбора словоформы.
+#[derive(Clone, Debug)]
+pub struct Parse<'a> {
+    /// Нормальная (исходная) форма слова, т.е. текстовое представление лексемы
+    pub normal_form: &'a str,
+
+    /// Полный тег словоформы: граммемы лексемы и граммемы словоформы
+    pub tag: Tag,
+
+    /// Числовой идентификатор лексемы
+    pub lemma_id: usize,
+
+    /// Порядковый номер словоформы в `Lemma::forms`
+    pub form_index: usize,
+}
+
+/// Морфологический анализатор, работающий по словарю.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::{Dict, MorphAnalyzer};
+///
+/// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+/// let analyzer = MorphAnalyzer::new(&dict);
+/// for parse in analyzer.parse("стали") {
+///     println!("{} {}", parse.normal_form, parse.tag);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MorphAnalyzer<'a> {
+    dict: &'a Dict,
+}
+
+impl<'a> MorphAnalyzer<'a> {
+    /// создаёт анализатор для словаря `dict`.
+    pub fn new(dict: &'a Dict) -> Self {
+        MorphAnalyzer { dict }
+    }
+
+    /// возвращает все варианты разбора слова `word`.
+    ///
+    /// Слово приводится к нижнему регистру. Если слово в словаре не найдено,
+    /// возвращается пустой список.
+    pub fn parse(&self, word: &str) -> Vec<Parse<'a>> {
+        let dict = self.dict;
+        let word = word.to_lowercase();
+        dict.form_entries(&word)
+            .map(|(lemma, form_idx)| {
+                let form = &lemma.forms[form_idx];
+                Parse {
+                    normal_form: &lemma.word,
+                    tag: lemma.tag(form),
+                    lemma_id: lemma.id,
+                    form_index: form_idx,
+                }
+            })
+            .collect()
+    }
+}
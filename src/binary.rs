@@ -0,0 +1,545 @@
+//! Бинарный формат словаря.
+//!
+//! Файл состоит из заголовка (сигнатура `OCDB`, номер версии формата, длина данных),
+//! данных и контрольной суммы CRC32 данных. Все числа записываются в порядке
+//! little-endian, строки — как длина (`u32`) и байты в кодировке UTF-8.
+//! Ссылки на граммемы, лексемы и типы связей записываются номерами элементов
+//! в соответствующих массивах словаря.
+//!
+//! Данные начинаются с небольших разделов (заголовок словаря, граммемы, ограничения,
+//! типы связей), которые читаются целиком. За ними следует таблица смещений записей
+//! лексем, сами записи и связи фиксированного размера. Благодаря таблице смещений
+//! к лексемам можно обращаться по номеру, не разбирая файл целиком
+//! (см. `MappedDict`).
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use crate::dict::{Dict, Form, Grammeme, Lemma, Link, LinkKind, Restriction, RestrictionKind,
+                  RestrictionScope};
+use crate::error::{Error, Result};
+use crate::grammeme_set::GrammemeSet;
+
+const MAGIC: &[u8; 4] = b"OCDB";
+const FORMAT_VERSION: u32 = 2;
+pub(crate) const HEADER_LEN: usize = 16;
+const CHECKSUM_LEN: usize = 4;
+
+/// Значение ссылки, обозначающее её отсутствие.
+const NONE: u32 = u32::MAX;
+
+struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn usize(&mut self, v: usize) {
+        self.u64(v as u64);
+    }
+
+    fn len(&mut self, len: usize) -> Result<()> {
+        if len >= NONE as usize {
+            return Err(Error::Binary(format!("too many elements: {}", len)));
+        }
+        self.u32(len as u32);
+        Ok(())
+    }
+
+    fn str(&mut self, s: &str) -> Result<()> {
+        self.len(s.len())?;
+        self.buf.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+pub(crate) struct Decoder<'a> {
+    pub(crate) data: &'a [u8],
+    pub(crate) pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub(crate) fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.pos > self.data.len() || self.data.len() - self.pos < len {
+            return Err(Error::Binary("unexpected end of data".to_owned()));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32> {
+        let mut b = [0; 4];
+        b.copy_from_slice(self.bytes(4)?);
+        Ok(u32::from_le_bytes(b))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64> {
+        let mut b = [0; 8];
+        b.copy_from_slice(self.bytes(8)?);
+        Ok(u64::from_le_bytes(b))
+    }
+
+    pub(crate) fn usize(&mut self) -> Result<usize> {
+        let v = self.u64()?;
+        if v > usize::MAX as u64 {
+            return Err(Error::Binary(format!("integer is too large: {}", v)));
+        }
+        Ok(v as usize)
+    }
+
+    pub(crate) fn str_ref(&mut self) -> Result<&'a str> {
+        let len = self.u32()? as usize;
+        Ok(std::str::from_utf8(self.bytes(len)?)?)
+    }
+
+    fn str(&mut self) -> Result<String> {
+        Ok(self.str_ref()?.to_owned())
+    }
+
+    pub(crate) fn item<T: Clone>(&mut self, items: &[T], what: &str) -> Result<T> {
+        let idx = self.u32()? as usize;
+        match items.get(idx) {
+            Some(item) => Ok(item.clone()),
+            None => Err(Error::Binary(format!("invalid {} index: {}", what, idx))),
+        }
+    }
+
+    pub(crate) fn optional_item<T: Clone>(&mut self, items: &[T], what: &str) -> Result<Option<T>> {
+        let idx = self.u32()?;
+        if idx == NONE {
+            return Ok(None);
+        }
+        match items.get(idx as usize) {
+            Some(item) => Ok(Some(item.clone())),
+            None => Err(Error::Binary(format!("invalid {} index: {}", what, idx))),
+        }
+    }
+}
+
+fn restriction_kind_to_u8(kind: RestrictionKind) -> u8 {
+    match kind {
+        RestrictionKind::Maybe => 0,
+        RestrictionKind::Obligatory => 1,
+        RestrictionKind::Forbidden => 2,
+    }
+}
+
+fn restriction_kind_from_u8(v: u8) -> Result<RestrictionKind> {
+    match v {
+        0 => Ok(RestrictionKind::Maybe),
+        1 => Ok(RestrictionKind::Obligatory),
+        2 => Ok(RestrictionKind::Forbidden),
+        _ => Err(Error::Binary(format!("invalid restriction kind: {}", v))),
+    }
+}
+
+fn restriction_scope_to_u8(scope: RestrictionScope) -> u8 {
+    match scope {
+        RestrictionScope::Lemma => 0,
+        RestrictionScope::Form => 1,
+    }
+}
+
+fn restriction_scope_from_u8(v: u8) -> Result<RestrictionScope> {
+    match v {
+        0 => Ok(RestrictionScope::Lemma),
+        1 => Ok(RestrictionScope::Form),
+        _ => Err(Error::Binary(format!("invalid restriction scope: {}", v))),
+    }
+}
+
+/// Размер записи связи: идентификатор, номера лексем и номер типа связи.
+pub(crate) const LINK_RECORD_LEN: usize = 8 + 4 + 4 + 4;
+
+fn encode(dict: &Dict) -> Result<Vec<u8>> {
+    let mut enc = Encoder { buf: Vec::new() };
+
+    let grammeme_idx: HashMap<&str, usize> = dict
+        .grammemes
+        .iter()
+        .enumerate()
+        .map(|(idx, g)| (g.name.as_str(), idx))
+        .collect();
+    let grammeme = |enc: &mut Encoder, g: &Grammeme| -> Result<()> {
+        match grammeme_idx.get(g.name.as_str()) {
+            Some(&idx) => {
+                enc.u32(idx as u32);
+                Ok(())
+            }
+            None => Err(Error::Binary(format!("unknown grammeme: '{}'", g.name))),
+        }
+    };
+    let grammemes = |enc: &mut Encoder, grammemes: &[Arc<Grammeme>]| -> Result<()> {
+        enc.len(grammemes.len())?;
+        for g in grammemes {
+            grammeme(enc, g)?;
+        }
+        Ok(())
+    };
+
+    enc.str(&dict.version)?;
+    enc.usize(dict.revision);
+
+    enc.len(dict.grammemes.len())?;
+    for g in &dict.grammemes {
+        match g.parent {
+            Some(ref parent) => {
+                enc.u8(1);
+                enc.str(parent)?;
+            }
+            None => enc.u8(0),
+        }
+        enc.str(&g.name)?;
+        enc.str(&g.alias)?;
+        enc.str(&g.description)?;
+    }
+
+    enc.len(dict.restrictions.len())?;
+    for r in &dict.restrictions {
+        enc.u8(restriction_kind_to_u8(r.kind));
+        enc.usize(r.auto);
+        enc.u8(restriction_scope_to_u8(r.left_scope));
+        match r.left_grammeme {
+            Some(ref g) => grammeme(&mut enc, g)?,
+            None => enc.u32(NONE),
+        }
+        enc.u8(restriction_scope_to_u8(r.right_scope));
+        match r.right_grammeme {
+            Some(ref g) => grammeme(&mut enc, g)?,
+            None => enc.u32(NONE),
+        }
+    }
+
+    enc.len(dict.link_kinds.len())?;
+    for kind in &dict.link_kinds {
+        enc.usize(kind.id);
+        enc.str(&kind.name)?;
+    }
+
+    // таблица смещений: начало каждой записи лексемы и начало раздела связей
+    enc.len(dict.lemmata.len())?;
+    let offsets_pos = enc.buf.len();
+    enc.buf.resize(offsets_pos + 8 * (dict.lemmata.len() + 1), 0);
+    let patch_offset = |enc: &mut Encoder, idx: usize| {
+        let pos = offsets_pos + 8 * idx;
+        let offset = enc.buf.len() as u64;
+        enc.buf[pos..pos + 8].copy_from_slice(&offset.to_le_bytes());
+    };
+
+    for (idx, lemma) in dict.lemmata.iter().enumerate() {
+        patch_offset(&mut enc, idx);
+        enc.usize(lemma.id);
+        enc.usize(lemma.revision);
+        enc.str(&lemma.word)?;
+        grammemes(&mut enc, &lemma.grammemes)?;
+        enc.len(lemma.forms.len())?;
+        for form in &lemma.forms {
+            enc.str(&form.word)?;
+            grammemes(&mut enc, &form.grammemes)?;
+        }
+    }
+    patch_offset(&mut enc, dict.lemmata.len());
+
+    let lemma_idx: HashMap<usize, usize> = dict
+        .lemmata
+        .iter()
+        .enumerate()
+        .map(|(idx, lemma)| (lemma.id, idx))
+        .collect();
+    let lemma = |enc: &mut Encoder, lemma: &Lemma| -> Result<()> {
+        match lemma_idx.get(&lemma.id) {
+            Some(&idx) => {
+                enc.u32(idx as u32);
+                Ok(())
+            }
+            None => Err(Error::Binary(format!("unknown lemma id: {}", lemma.id))),
+        }
+    };
+    enc.len(dict.links.len())?;
+    for link in &dict.links {
+        enc.usize(link.id);
+        lemma(&mut enc, &link.from)?;
+        lemma(&mut enc, &link.to)?;
+        match dict.link_kinds.iter().position(|kind| kind == &link.kind) {
+            Some(idx) => enc.u32(idx as u32),
+            None => return Err(Error::UnknownLinkKind(link.kind.id)),
+        }
+    }
+
+    Ok(enc.buf)
+}
+
+/// Небольшие разделы бинарного словаря, которые читаются целиком,
+/// и расположение остальных разделов.
+pub(crate) struct Header {
+    pub(crate) version: String,
+    pub(crate) revision: usize,
+    pub(crate) grammemes: Vec<Arc<Grammeme>>,
+    pub(crate) restrictions: Vec<Restriction>,
+    pub(crate) link_kinds: Vec<Arc<LinkKind>>,
+    pub(crate) lemmata_count: usize,
+    /// Позиция таблицы смещений лексем
+    pub(crate) offsets_pos: usize,
+    pub(crate) links_count: usize,
+    /// Позиция первой записи связи
+    pub(crate) links_pos: usize,
+}
+
+impl Header {
+    /// возвращает границы записи лексемы с номером `idx`.
+    pub(crate) fn lemma_range(&self, data: &[u8], idx: usize) -> Result<std::ops::Range<usize>> {
+        let mut dec = Decoder {
+            data,
+            pos: self.offsets_pos + 8 * idx,
+        };
+        let start = dec.usize()?;
+        let end = dec.usize()?;
+        if start > end || end > self.links_pos {
+            return Err(Error::Binary(format!("invalid lemma offset: {}", idx)));
+        }
+        Ok(start..end)
+    }
+
+    /// разбирает запись лексемы.
+    pub(crate) fn decode_lemma(&self, record: &[u8]) -> Result<Lemma> {
+        let mut dec = Decoder { data: record, pos: 0 };
+        let mut lemma = Lemma {
+            id: dec.usize()?,
+            revision: dec.usize()?,
+            word: dec.str()?,
+            grammemes: self.decode_grammemes(&mut dec)?,
+            forms: Vec::new(),
+        };
+        let forms_count = dec.u32()?;
+        for _ in 0..forms_count {
+            lemma.forms.push(Form {
+                word: dec.str()?,
+                grammemes: self.decode_grammemes(&mut dec)?,
+            });
+        }
+        if dec.pos != record.len() {
+            return Err(Error::Binary(format!("invalid lemma record: {}", lemma.id)));
+        }
+        Ok(lemma)
+    }
+
+    fn decode_grammemes(&self, dec: &mut Decoder) -> Result<Vec<Arc<Grammeme>>> {
+        let count = dec.u32()?;
+        (0..count).map(|_| dec.item(&self.grammemes, "grammeme")).collect()
+    }
+
+    /// разбирает запись связи с номером `idx` и возвращает её идентификатор,
+    /// номера лексем и тип.
+    pub(crate) fn decode_link(
+        &self,
+        data: &[u8],
+        idx: usize,
+    ) -> Result<(usize, usize, usize, Arc<LinkKind>)> {
+        let mut dec = Decoder {
+            data,
+            pos: self.links_pos + LINK_RECORD_LEN * idx,
+        };
+        let id = dec.usize()?;
+        let from = dec.u32()? as usize;
+        let to = dec.u32()? as usize;
+        if from >= self.lemmata_count || to >= self.lemmata_count {
+            return Err(Error::Binary(format!("invalid lemma index in link: {}", id)));
+        }
+        let kind = dec.item(&self.link_kinds, "link kind")?;
+        Ok((id, from, to, kind))
+    }
+}
+
+/// проверяет заголовок и контрольную сумму и возвращает данные словаря.
+pub(crate) fn payload(file: &[u8]) -> Result<&[u8]> {
+    let data = unverified_payload(file)?;
+    let mut dec = Decoder {
+        data: file,
+        pos: HEADER_LEN + data.len(),
+    };
+    if crc32fast::hash(data) != dec.u32()? {
+        return Err(Error::Binary("checksum mismatch".to_owned()));
+    }
+    Ok(data)
+}
+
+/// проверяет заголовок и длину файла и возвращает данные словаря,
+/// не проверяя контрольную сумму.
+pub(crate) fn unverified_payload(file: &[u8]) -> Result<&[u8]> {
+    if file.len() < HEADER_LEN || &file[0..4] != MAGIC {
+        return Err(Error::Binary("invalid signature".to_owned()));
+    }
+    let mut dec = Decoder { data: file, pos: 4 };
+    let version = dec.u32()?;
+    if version != FORMAT_VERSION {
+        return Err(Error::Binary(format!("unsupported format version: {}", version)));
+    }
+    let len = dec.usize()?;
+    if file.len() - HEADER_LEN < CHECKSUM_LEN || file.len() - HEADER_LEN - CHECKSUM_LEN != len {
+        return Err(Error::Binary("invalid data length".to_owned()));
+    }
+    Ok(&file[HEADER_LEN..HEADER_LEN + len])
+}
+
+/// читает небольшие разделы словаря и расположение остальных разделов.
+pub(crate) fn decode_header(data: &[u8]) -> Result<Header> {
+    let mut dec = Decoder { data, pos: 0 };
+    let version = dec.str()?;
+    let revision = dec.usize()?;
+
+    let mut grammemes = Vec::new();
+    let count = dec.u32()?;
+    if count as usize > GrammemeSet::CAPACITY {
+        return Err(Error::TooManyGrammemes);
+    }
+    for _ in 0..count {
+        let parent = match dec.u8()? {
+            0 => None,
+            _ => Some(dec.str()?),
+        };
+        grammemes.push(Arc::new(Grammeme {
+            parent,
+            name: dec.str()?,
+            alias: dec.str()?,
+            description: dec.str()?,
+            index: grammemes.len(),
+        }));
+    }
+
+    let mut restrictions = Vec::new();
+    let count = dec.u32()?;
+    for _ in 0..count {
+        restrictions.push(Restriction {
+            kind: restriction_kind_from_u8(dec.u8()?)?,
+            auto: dec.usize()?,
+            left_scope: restriction_scope_from_u8(dec.u8()?)?,
+            left_grammeme: dec.optional_item(&grammemes, "grammeme")?,
+            right_scope: restriction_scope_from_u8(dec.u8()?)?,
+            right_grammeme: dec.optional_item(&grammemes, "grammeme")?,
+        });
+    }
+
+    let mut link_kinds = Vec::new();
+    let count = dec.u32()?;
+    for _ in 0..count {
+        link_kinds.push(Arc::new(LinkKind {
+            id: dec.usize()?,
+            name: dec.str()?,
+        }));
+    }
+
+    let lemmata_count = dec.u32()? as usize;
+    let offsets_pos = dec.pos;
+    dec.pos = offsets_pos + 8 * lemmata_count;
+    let links_section = dec.usize()?;
+    if links_section > data.len() {
+        return Err(Error::Binary("invalid links offset".to_owned()));
+    }
+    dec.pos = links_section;
+    let links_count = dec.u32()? as usize;
+    let links_pos = dec.pos;
+    if data.len() - links_pos != links_count * LINK_RECORD_LEN {
+        return Err(Error::Binary("invalid links section".to_owned()));
+    }
+
+    Ok(Header {
+        version,
+        revision,
+        grammemes,
+        restrictions,
+        link_kinds,
+        lemmata_count,
+        offsets_pos,
+        links_count,
+        links_pos,
+    })
+}
+
+fn decode(data: &[u8]) -> Result<Dict> {
+    let mut header = decode_header(data)?;
+
+    let mut lemmata = Vec::new();
+    for idx in 0..header.lemmata_count {
+        let range = header.lemma_range(data, idx)?;
+        lemmata.push(Arc::new(header.decode_lemma(&data[range])?));
+    }
+
+    let mut dict = Dict {
+        version: std::mem::take(&mut header.version),
+        revision: header.revision,
+        grammemes: std::mem::take(&mut header.grammemes),
+        restrictions: std::mem::take(&mut header.restrictions),
+        lemmata,
+        link_kinds: header.link_kinds.clone(),
+        ..Dict::default()
+    };
+    // лексемы согласуются со словарём до того, как на них сошлются связи
+    dict.reindex()?;
+
+    let mut links = Vec::new();
+    for idx in 0..header.links_count {
+        let (id, from, to, kind) = header.decode_link(data, idx)?;
+        links.push(Link {
+            id,
+            from: dict.lemmata[from].clone(),
+            to: dict.lemmata[to].clone(),
+            kind,
+        });
+    }
+    dict.links = links;
+    Ok(dict)
+}
+
+impl Dict {
+    /// сохраняет словарь в бинарном формате.
+    ///
+    /// Загрузка словаря из бинарного формата методом
+    /// [`load_binary`](#method.load_binary) во много раз быстрее разбора XML.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use std::io::BufWriter;
+    ///
+    /// use opencorpora::Dict;
+    ///
+    /// let dict = Dict::read_from_xml(File::open("dict.xml").unwrap()).unwrap();
+    /// let out = BufWriter::new(File::create("dict.bin").unwrap());
+    /// dict.save_binary(out).unwrap();
+    /// ```
+    pub fn save_binary<W: Write>(&self, mut w: W) -> Result<()> {
+        let data = encode(self)?;
+        w.write_all(MAGIC)?;
+        w.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        w.write_all(&(data.len() as u64).to_le_bytes())?;
+        w.write_all(&data)?;
+        w.write_all(&crc32fast::hash(&data).to_le_bytes())?;
+        w.flush()?;
+        Ok(())
+    }
+
+    /// читает словарь, сохранённый методом [`save_binary`](#method.save_binary).
+    pub fn load_binary<R: Read>(mut r: R) -> Result<Dict> {
+        let mut file = Vec::new();
+        r.read_to_end(&mut file)?;
+        decode(payload(&file)?)
+    }
+}
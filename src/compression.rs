@@ -0,0 +1,82 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::dict::Dict;
+use crate::error::{Error, Result};
+
+/// Способ сжатия файла словаря.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Compression {
+    None,
+    Bzip2,
+    Gzip,
+    Xz,
+    Zstd,
+}
+
+impl Compression {
+    /// определяет способ сжатия по первым байтам файла. Файл, начало которого
+    /// не подходит ни под один из форматов, считается несжатым независимо от расширения.
+    fn detect(magic: &[u8]) -> Compression {
+        if magic.starts_with(b"BZh") {
+            Compression::Bzip2
+        } else if magic.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if magic.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Compression::Xz
+        } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    /// возвращает ошибку о том, что поддержка формата не включена.
+    fn unsupported(self) -> Error {
+        let (format, feature) = match self {
+            Compression::None => ("plain", ""),
+            Compression::Bzip2 => ("bzip2", "bzip2"),
+            Compression::Gzip => ("gzip", "gzip"),
+            Compression::Xz => ("xz", "xz"),
+            Compression::Zstd => ("zstd", "zstd"),
+        };
+        Error::UnsupportedCompression { format, feature }
+    }
+}
+
+impl Dict {
+    /// открывает файл словаря и читает его, распаковывая на лету.
+    ///
+    /// Способ сжатия (bzip2, gzip, xz или zstd) определяется по первым байтам
+    /// файла, а не по расширению. Поддержка каждого формата включается
+    /// отдельной cargo-опцией: `bzip2`, `gzip`, `xz` и `zstd`. Несжатый файл
+    /// читается как [`read_from_xml`](#method.read_from_xml).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use opencorpora::Dict;
+    ///
+    /// let dict = Dict::open("dict.opcorpora.xml.bz2").unwrap();
+    /// println!("Lemmata count: {}", dict.lemmata.len());
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Dict> {
+        let path = path.as_ref();
+        let mut file = BufReader::new(File::open(path)?);
+        let compression = Compression::detect(file.fill_buf()?);
+        match compression {
+            Compression::None => Dict::read_from_xml(file),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Dict::read_from_xml(bzip2::bufread::MultiBzDecoder::new(file)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Dict::read_from_xml(flate2::bufread::MultiGzDecoder::new(file)),
+            #[cfg(feature = "xz")]
+            Compression::Xz => Dict::read_from_xml(xz2::bufread::XzDecoder::new_multi_decoder(file)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Dict::read_from_xml(zstd::stream::read::Decoder::with_buffer(file)?),
+            #[allow(unreachable_patterns)]
+            compression => Err(compression.unsupported()),
+        }
+    }
+}
@@ -1,10 +1,16 @@
-use std::rc::Rc;
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::index::Index;
 
 /// Структура описывающая граммему.
 ///
 /// Список всех граммем можно [посмотреть](http://opencorpora.org/dict.php?act=gram)
 /// на сайте [opencorpora.org](http://opencorpora.org).
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Grammeme {
     /// Имя родительской граммемы (значение `None` индицирует, что это граммема верхнего уровня)
     pub parent: Option<String>,
@@ -17,10 +23,36 @@ pub struct Grammeme {
 
     /// Подробное описание граммемы
     pub description: String,
+
+    /// Порядковый номер граммемы в `Dict::grammemes`.
+    /// Используется как номер бита в [`GrammemeSet`](struct.GrammemeSet.html).
+    /// Заполняется только при чтении словаря и методом [`Dict::reindex`](struct.Dict.html#method.reindex);
+    /// у граммем, созданных отдельно от словаря или десериализованных, номер
+    /// равен `usize::MAX` и в множества граммем они не попадают.
+    #[cfg_attr(feature = "serde", serde(skip, default = "unassigned_index"))]
+    pub index: usize,
+}
+
+/// номер граммемы, ещё не получившей позицию в словаре.
+fn unassigned_index() -> usize {
+    usize::MAX
+}
+
+impl Default for Grammeme {
+    fn default() -> Self {
+        Grammeme {
+            parent: None,
+            name: String::new(),
+            alias: String::new(),
+            description: String::new(),
+            index: unassigned_index(),
+        }
+    }
 }
 
 /// Тип ограничения на использование граммемы.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RestrictionKind {
     /// Необязательный
     Maybe,
@@ -33,7 +65,8 @@ pub enum RestrictionKind {
 }
 
 /// Область ограничения на использование граммемы.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RestrictionScope {
     /// Лексема
     Lemma,
@@ -46,7 +79,8 @@ pub enum RestrictionScope {
 ///
 /// Более подробное описание [приведено](http://opencorpora.org/dict.php?act=gram_restr)
 /// на сайте [opencorpora.org](http://opencorpora.org).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Restriction {
     /// Тип ограничения (см. [документацию](enum.RestrictionKind.html) типа `RestrictionKind`)
     pub kind: RestrictionKind,
@@ -59,14 +93,16 @@ pub struct Restriction {
 
     /// Граммема слева.
     /// Для некоторых правил значение справа может отсутствовать.
-    pub left_grammeme: Option<Rc<Grammeme>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::optional_grammeme"))]
+    pub left_grammeme: Option<Arc<Grammeme>>,
 
     /// Область применения справа
     pub right_scope: RestrictionScope,
 
     /// Граммема справа.
     /// Для некоторых правил значение справа может отсутствовать.
-    pub right_grammeme: Option<Rc<Grammeme>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::optional_grammeme"))]
+    pub right_grammeme: Option<Arc<Grammeme>>,
 }
 
 impl Default for Restriction {
@@ -83,17 +119,20 @@ impl Default for Restriction {
 }
 
 /// Структура словоформы лексемы.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Form {
     /// Текстовое представление словоформы
     pub word: String,
 
     /// Множество граммем описывающих словоформу
-    pub grammemes: Vec<Rc<Grammeme>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::grammemes"))]
+    pub grammemes: Vec<Arc<Grammeme>>,
 }
 
 /// Структура описывающая лексему.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Lemma {
     /// Числовой идентификатор лексемы
     pub id: usize,
@@ -105,14 +144,16 @@ pub struct Lemma {
     pub word: String,
 
     /// Множество граммем описывающих лексему
-    pub grammemes: Vec<Rc<Grammeme>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::grammemes"))]
+    pub grammemes: Vec<Arc<Grammeme>>,
 
     /// Множество словоформ входящих в данную лексему
     pub forms: Vec<Form>,
 }
 
 /// Тип связи между лексемами.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LinkKind {
     /// Числовой идентификатор типа связи.
     /// Используется в типе `Link`.
@@ -123,23 +164,55 @@ pub struct LinkKind {
 }
 
 /// Структура хранящая связь между двумя лексемами.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Link {
     /// Числовой идентификатор связи
     pub id: usize,
 
     /// Лексема с исходной стороны связи
-    pub from: Rc<Lemma>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::lemma"))]
+    pub from: Arc<Lemma>,
 
     /// Лексема с конечной стороны связи
-    pub to: Rc<Lemma>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::lemma"))]
+    pub to: Arc<Lemma>,
 
     /// Типа связи
-    pub kind: Rc<LinkKind>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::link_kind"))]
+    pub kind: Arc<LinkKind>,
 }
 
 /// Структура содержащая данные словаря.
+///
+/// Перекрёстные ссылки между элементами словаря хранятся в `Arc`, поэтому словарь
+/// реализует `Send` и `Sync` и может разделяться между потоками через `Arc<Dict>`
+/// или храниться в `static`.
+///
+/// При включённой опции `serde` ссылки на граммемы сериализуются их именами,
+/// а ссылки на лексемы и типы связей — идентификаторами. При десериализации
+/// словаря они заменяются ссылками на соответствующие элементы словаря.
+/// Отдельно десериализованные `Lemma`, `Form`, `Restriction` и `Link`
+/// содержат вместо ссылок заглушки, у которых заполнено только имя или идентификатор.
+/// Заглушки граммем не входят ни в одно множество граммем, поэтому граммемы
+/// отдельно десериализованной лексемы нужно заменить граммемами словаря методом
+/// [`resolve`](#method.resolve).
+///
+/// Кроме открытых полей словарь хранит индексы для поиска, поэтому вне библиотеки
+/// его нельзя создать литералом структуры. Словарь создаётся методом
+/// `Dict::default()`, после заполнения полей индексы строятся
+/// методом [`reindex`](#method.reindex):
+///
+/// ```
+/// use opencorpora::Dict;
+///
+/// let mut dict = Dict::default();
+/// dict.version = "0.92".to_owned();
+/// dict.reindex().unwrap();
+/// ```
 #[derive(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serialization::DictData"))]
 pub struct Dict {
     /// Версия словаря
     pub version: String,
@@ -148,17 +221,34 @@ pub struct Dict {
     pub revision: usize,
 
     /// Множество граммем
-    pub grammemes: Vec<Rc<Grammeme>>,
+    pub grammemes: Vec<Arc<Grammeme>>,
 
     /// Множество правил-ограничений на использование граммем
     pub restrictions: Vec<Restriction>,
 
     /// Массив лексем
-    pub lemmata: Vec<Rc<Lemma>>,
+    pub lemmata: Vec<Arc<Lemma>>,
 
     /// Множество типов связей между лексемами
-    pub link_kinds: Vec<Rc<LinkKind>>,
+    pub link_kinds: Vec<Arc<LinkKind>>,
 
     /// Множество связей между лексемами
     pub links: Vec<Link>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) index: Index,
 }
+
+impl PartialEq for Dict {
+    fn eq(&self, other: &Dict) -> bool {
+        self.version == other.version
+            && self.revision == other.revision
+            && self.grammemes == other.grammemes
+            && self.restrictions == other.restrictions
+            && self.lemmata == other.lemmata
+            && self.link_kinds == other.link_kinds
+            && self.links == other.links
+    }
+}
+
+impl Eq for Dict {}
@@ -1,5 +1,9 @@
+use std::fmt;
+
 use thiserror::Error;
 
+use crate::reader::ParsingState;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("XML parser error")]
@@ -26,8 +30,108 @@ pub enum Error {
         source: std::num::ParseIntError,
     },
 
+    /// Некорректное содержимое XML-словаря (неизвестный тег, недопустимое значение атрибута).
+    /// Ошибки читателя возвращаются обёрнутыми в [`Located`](#variant.Located).
     #[error("XML dictionary parsing error: {0}")]
     Parsing(String),
+
+    #[error("unexpected end of file")]
+    UnexpectedEof,
+
+    #[error("unexpected XML event: {0}")]
+    UnexpectedEvent(String),
+
+    /// Ошибка читателя XML-словаря вместе с местом, где она произошла.
+    /// Исходная ошибка (например, [`Parsing`](#variant.Parsing)) хранится
+    /// в поле [`ParseError::source`](struct.ParseError.html#structfield.source).
+    #[error("{0}")]
+    Located(Box<ParseError>),
+
+    #[error("unknown grammeme: '{0}'")]
+    UnknownGrammeme(String),
+
+    #[error("unknown lemma id: {0}")]
+    UnknownLemma(usize),
+
+    #[error("unknown link type id: {0}")]
+    UnknownLinkKind(usize),
+
+    #[error("lemma {id} has revision {actual}, expected {expected}")]
+    RevisionMismatch {
+        id: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("unknown grammeme '{grammeme}' at position {position} in tag '{tag}'")]
+    InvalidTag {
+        tag: String,
+        grammeme: String,
+        position: usize,
+    },
+
+    #[error("invalid grammeme hierarchy: {0}")]
+    GrammemeTree(String),
+
+    #[error("too many grammemes: more than {}", crate::grammeme_set::GrammemeSet::CAPACITY)]
+    TooManyGrammemes,
+
+    #[error("I/O error")]
+    Io {
+        #[from]
+        source: std::io::Error,
+    },
+
+    #[error("binary dictionary format error: {0}")]
+    Binary(String),
+
+    #[error("dictionary is {format}-compressed, enable the '{feature}' feature to read it")]
+    UnsupportedCompression {
+        format: &'static str,
+        feature: &'static str,
+    },
+}
+
+/// Ошибка чтения XML-словаря с указанием места, где она произошла.
+#[derive(Debug)]
+pub struct ParseError {
+    /// Смещение в байтах от начала файла до XML-события, вызвавшего ошибку
+    pub offset: usize,
+
+    /// Номер строки (начиная с 1)
+    pub line: usize,
+
+    /// Номер столбца в символах (начиная с 1)
+    pub column: usize,
+
+    /// Состояние читателя в момент ошибки
+    pub state: ParsingState,
+
+    /// Идентификатор лексемы, внутри которой произошла ошибка
+    pub lemma_id: Option<usize>,
+
+    /// Исходная ошибка
+    pub source: Error,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {} (byte {}, state {:?}",
+            self.source, self.line, self.column, self.offset, self.state
+        )?;
+        if let Some(id) = self.lemma_id {
+            write!(f, ", lemma {}", id)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
 }
 
 pub type Result<R> = std::result::Result<R, Error>;
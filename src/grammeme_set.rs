@@ -0,0 +1,220 @@
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Sub};
+use std::sync::Arc;
+
+use crate::dict::{Dict, Form, Grammeme, Lemma};
+use crate::error::{Error, Result};
+
+const WORDS: usize = 4;
+
+/// Множество граммем в виде битовой маски.
+///
+/// Граммема представлена битом с номером [`Grammeme::index`](struct.Grammeme.html#structfield.index),
+/// т.е. её позицией в `Dict::grammemes`, поэтому проверка принадлежности и операции
+/// над множествами выполняются за постоянное время. Множества, построенные
+/// по разным словарям, сравнивать нельзя.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::Dict;
+///
+/// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+/// let plur_gent = dict.grammeme_set(["plur", "gent"]).unwrap();
+/// for lemma in &dict.lemmata {
+///     let lemma_set = lemma.grammeme_set();
+///     for form in &lemma.forms {
+///         if (lemma_set | form.grammeme_set()).is_superset(&plur_gent) {
+///             println!("{}", form.word);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct GrammemeSet {
+    bits: [u64; WORDS],
+}
+
+impl GrammemeSet {
+    /// Максимальное количество граммем в словаре, которое может быть представлено множеством.
+    /// Граммемы с бо́льшими номерами в множество не добавляются.
+    pub const CAPACITY: usize = 64 * WORDS;
+
+    /// создаёт пустое множество.
+    pub fn new() -> Self {
+        GrammemeSet::default()
+    }
+
+    /// добавляет граммему в множество.
+    /// Возвращает `false`, если граммема уже была в множестве или её номер
+    /// не меньше [`CAPACITY`](#associatedconstant.CAPACITY).
+    pub fn insert(&mut self, grammeme: &Grammeme) -> bool {
+        self.insert_index(grammeme.index)
+    }
+
+    /// добавляет в множество граммему с позицией `index` в `Dict::grammemes`.
+    pub(crate) fn insert_index(&mut self, index: usize) -> bool {
+        let (word, bit) = match Self::position(index) {
+            Some(position) => position,
+            None => return false,
+        };
+        let absent = self.bits[word] & bit == 0;
+        self.bits[word] |= bit;
+        absent
+    }
+
+    /// удаляет граммему из множества.
+    /// Возвращает `false`, если граммемы не было в множестве.
+    pub fn remove(&mut self, grammeme: &Grammeme) -> bool {
+        let (word, bit) = match Self::position(grammeme.index) {
+            Some(position) => position,
+            None => return false,
+        };
+        let present = self.bits[word] & bit != 0;
+        self.bits[word] &= !bit;
+        present
+    }
+
+    /// проверяет, входит ли граммема в множество.
+    pub fn contains(&self, grammeme: &Grammeme) -> bool {
+        self.contains_index(grammeme.index)
+    }
+
+    /// проверяет, входит ли в множество граммема с позицией `index` в `Dict::grammemes`.
+    pub fn contains_index(&self, index: usize) -> bool {
+        match Self::position(index) {
+            Some((word, bit)) => self.bits[word] & bit != 0,
+            None => false,
+        }
+    }
+
+    /// возвращает объединение множеств.
+    pub fn union(&self, other: &GrammemeSet) -> GrammemeSet {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// возвращает пересечение множеств.
+    pub fn intersection(&self, other: &GrammemeSet) -> GrammemeSet {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// возвращает граммемы, входящие в это множество, но не входящие в `other`.
+    pub fn difference(&self, other: &GrammemeSet) -> GrammemeSet {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    /// проверяет, что все граммемы множества входят в `other`.
+    pub fn is_subset(&self, other: &GrammemeSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// проверяет, что все граммемы `other` входят в множество.
+    pub fn is_superset(&self, other: &GrammemeSet) -> bool {
+        other.is_subset(self)
+    }
+
+    /// проверяет, что у множеств нет общих граммем.
+    pub fn is_disjoint(&self, other: &GrammemeSet) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    /// возвращает количество граммем в множестве.
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// проверяет, пустое ли множество.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&word| word == 0)
+    }
+
+    /// возвращает позиции граммем множества в `Dict::grammemes` по возрастанию.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..Self::CAPACITY).filter(move |&index| self.contains_index(index))
+    }
+
+    fn position(index: usize) -> Option<(usize, u64)> {
+        if index >= Self::CAPACITY {
+            return None;
+        }
+        Some((index / 64, 1 << (index % 64)))
+    }
+
+    fn zip_with<F: Fn(u64, u64) -> u64>(&self, other: &GrammemeSet, f: F) -> GrammemeSet {
+        let mut result = GrammemeSet::new();
+        for (word, (&a, &b)) in self.bits.iter().zip(&other.bits).enumerate() {
+            result.bits[word] = f(a, b);
+        }
+        result
+    }
+}
+
+impl std::fmt::Debug for GrammemeSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.indices()).finish()
+    }
+}
+
+impl BitOr for GrammemeSet {
+    type Output = GrammemeSet;
+
+    fn bitor(self, other: GrammemeSet) -> GrammemeSet {
+        self.union(&other)
+    }
+}
+
+impl BitAnd for GrammemeSet {
+    type Output = GrammemeSet;
+
+    fn bitand(self, other: GrammemeSet) -> GrammemeSet {
+        self.intersection(&other)
+    }
+}
+
+impl Sub for GrammemeSet {
+    type Output = GrammemeSet;
+
+    fn sub(self, other: GrammemeSet) -> GrammemeSet {
+        self.difference(&other)
+    }
+}
+
+impl<'a> FromIterator<&'a Arc<Grammeme>> for GrammemeSet {
+    fn from_iter<I: IntoIterator<Item = &'a Arc<Grammeme>>>(iter: I) -> Self {
+        let mut set = GrammemeSet::new();
+        for grammeme in iter {
+            set.insert(grammeme);
+        }
+        set
+    }
+}
+
+impl Lemma {
+    /// возвращает множество граммем лексемы.
+    pub fn grammeme_set(&self) -> GrammemeSet {
+        self.grammemes.iter().collect()
+    }
+}
+
+impl Form {
+    /// возвращает множество граммем словоформы.
+    pub fn grammeme_set(&self) -> GrammemeSet {
+        self.grammemes.iter().collect()
+    }
+}
+
+impl Dict {
+    /// строит множество граммем по их именам на латинице.
+    pub fn grammeme_set<'a, I: IntoIterator<Item = &'a str>>(&self, names: I) -> Result<GrammemeSet> {
+        let mut set = GrammemeSet::new();
+        for name in names {
+            match self.grammeme(name) {
+                Some(grammeme) => {
+                    set.insert(grammeme);
+                }
+                None => return Err(Error::UnknownGrammeme(name.to_owned())),
+            }
+        }
+        Ok(set)
+    }
+}
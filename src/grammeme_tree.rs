@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::dict::{Dict, Grammeme};
+use crate::error::{Error, Result};
+
+/// Дерево граммем, построенное по полю `Grammeme::parent`.
+///
+/// Граммемы верхнего уровня (категории вроде `POST` или `CAse`) являются корнями,
+/// конкретные значения категорий — их потомками.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::Dict;
+///
+/// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+/// let tree = dict.grammeme_tree().unwrap();
+/// assert!(tree.is_a("nomn", "CAse"));
+/// for case in tree.children("CAse") {
+///     println!("{} ({})", case.name, case.alias);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GrammemeTree {
+    grammemes: Vec<Arc<Grammeme>>,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    by_name: HashMap<String, usize>,
+}
+
+impl GrammemeTree {
+    /// строит дерево граммем.
+    ///
+    /// Возвращает ошибку, если родительская граммема не найдена
+    /// или если родительские связи образуют цикл.
+    pub fn new(grammemes: &[Arc<Grammeme>]) -> Result<GrammemeTree> {
+        let by_name: HashMap<String, usize> = grammemes
+            .iter()
+            .enumerate()
+            .map(|(idx, g)| (g.name.clone(), idx))
+            .collect();
+
+        let mut parents = Vec::with_capacity(grammemes.len());
+        let mut children = vec![Vec::new(); grammemes.len()];
+        for (idx, grammeme) in grammemes.iter().enumerate() {
+            let parent = match grammeme.parent {
+                Some(ref parent) => match by_name.get(parent) {
+                    Some(&parent_idx) => Some(parent_idx),
+                    None => {
+                        return Err(Error::GrammemeTree(format!(
+                            "parent '{}' of grammeme '{}' isn't found",
+                            parent, grammeme.name
+                        )));
+                    }
+                },
+                None => None,
+            };
+            if let Some(parent_idx) = parent {
+                children[parent_idx].push(idx);
+            }
+            parents.push(parent);
+        }
+
+        for (idx, grammeme) in grammemes.iter().enumerate() {
+            let mut current = parents[idx];
+            let mut steps = 0;
+            while let Some(parent_idx) = current {
+                steps += 1;
+                if steps > grammemes.len() {
+                    return Err(Error::GrammemeTree(format!(
+                        "grammeme '{}' is part of a cycle",
+                        grammeme.name
+                    )));
+                }
+                current = parents[parent_idx];
+            }
+        }
+
+        Ok(GrammemeTree {
+            grammemes: grammemes.to_vec(),
+            parents,
+            children,
+            by_name,
+        })
+    }
+
+    /// возвращает граммему по имени.
+    pub fn get(&self, name: &str) -> Option<&Arc<Grammeme>> {
+        self.by_name.get(name).map(|&idx| &self.grammemes[idx])
+    }
+
+    /// возвращает граммемы верхнего уровня.
+    pub fn roots(&self) -> impl Iterator<Item = &Arc<Grammeme>> {
+        self.grammemes
+            .iter()
+            .zip(&self.parents)
+            .filter(|(_, parent)| parent.is_none())
+            .map(|(g, _)| g)
+    }
+
+    /// возвращает родительскую граммему.
+    pub fn parent(&self, name: &str) -> Option<&Arc<Grammeme>> {
+        let idx = *self.by_name.get(name)?;
+        self.parents[idx].map(|parent_idx| &self.grammemes[parent_idx])
+    }
+
+    /// возвращает непосредственных потомков граммемы.
+    pub fn children(&self, name: &str) -> impl Iterator<Item = &Arc<Grammeme>> {
+        let children = match self.by_name.get(name) {
+            Some(&idx) => self.children[idx].as_slice(),
+            None => &[],
+        };
+        children.iter().map(move |&idx| &self.grammemes[idx])
+    }
+
+    /// возвращает всех предков граммемы, начиная с родительской.
+    pub fn ancestors(&self, name: &str) -> impl Iterator<Item = &Arc<Grammeme>> {
+        let mut current = self.by_name.get(name).and_then(|&idx| self.parents[idx]);
+        std::iter::from_fn(move || {
+            let idx = current?;
+            current = self.parents[idx];
+            Some(&self.grammemes[idx])
+        })
+    }
+
+    /// возвращает всех потомков граммемы в порядке обхода в глубину.
+    pub fn descendants(&self, name: &str) -> impl Iterator<Item = &Arc<Grammeme>> {
+        let mut stack: Vec<usize> = match self.by_name.get(name) {
+            Some(&idx) => self.children[idx].iter().rev().copied().collect(),
+            None => Vec::new(),
+        };
+        std::iter::from_fn(move || {
+            let idx = stack.pop()?;
+            stack.extend(self.children[idx].iter().rev());
+            Some(&self.grammemes[idx])
+        })
+    }
+
+    /// возвращает глубину граммемы в дереве (0 для граммем верхнего уровня).
+    pub fn depth(&self, name: &str) -> Option<usize> {
+        self.by_name.get(name)?;
+        Some(self.ancestors(name).count())
+    }
+
+    /// проверяет, совпадает ли граммема `name` с `ancestor` или является его потомком.
+    pub fn is_a(&self, name: &str, ancestor: &str) -> bool {
+        self.by_name.contains_key(name)
+            && (name == ancestor || self.ancestors(name).any(|g| g.name == ancestor))
+    }
+}
+
+impl Dict {
+    /// строит дерево граммем словаря.
+    pub fn grammeme_tree(&self) -> Result<GrammemeTree> {
+        GrammemeTree::new(&self.grammemes)
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::dict::{Dict, Form, Grammeme, Lemma, LinkKind};
+use crate::error::{Error, Result};
+use crate::grammeme_set::GrammemeSet;
+
+/// Индексы для быстрого поиска по словарю.
+///
+/// Строятся методом [`Dict::reindex`](struct.Dict.html#method.reindex).
+/// Индексы хранят позиции в открытых полях словаря и устаревают при их изменении,
+/// поэтому при каждом поиске найденный элемент сверяется с ключом:
+/// устаревшая позиция даёт пустой результат, а не панику или чужой элемент.
+///
+/// Индекс словоформ хранит не сами тексты словоформ, а их хеши: тексты уже есть
+/// в лексемах, и их копии удвоили бы память, занятую словоформами.
+/// Словоформы с совпадающим хешем отсеиваются при поиске.
+#[derive(Default)]
+pub(crate) struct Index {
+    /// Позиции (номер лексемы, номер словоформы) по хешу текстового представления словоформы
+    forms: HashMap<u64, Vec<(usize, usize)>>,
+
+    /// Позиции в `Dict::lemmata` по идентификатору лексемы
+    lemmata: HashMap<usize, usize>,
+
+    /// Позиции в `Dict::link_kinds` по идентификатору типа связи
+    link_kinds: HashMap<usize, usize>,
+
+    /// Позиции в `Dict::grammemes` по имени граммемы на латинице
+    grammemes: HashMap<String, usize>,
+
+    /// Позиции в `Dict::grammemes` по имени граммемы на кириллице
+    aliases: HashMap<String, usize>,
+}
+
+impl std::fmt::Debug for Index {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Index")
+            .field("forms", &self.forms.len())
+            .field("lemmata", &self.lemmata.len())
+            .field("link_kinds", &self.link_kinds.len())
+            .field("grammemes", &self.grammemes.len())
+            .finish()
+    }
+}
+
+impl Index {
+    fn build(dict: &Dict) -> Index {
+        let mut forms = HashMap::<u64, Vec<(usize, usize)>>::new();
+        for (lemma_idx, lemma) in dict.lemmata.iter().enumerate() {
+            for (form_idx, form) in lemma.forms.iter().enumerate() {
+                forms
+                    .entry(form_hash(&form.word))
+                    .or_default()
+                    .push((lemma_idx, form_idx));
+            }
+        }
+        let lemmata = dict.lemmata.iter().enumerate().map(|(idx, l)| (l.id, idx)).collect();
+        let link_kinds = dict.link_kinds.iter().enumerate().map(|(idx, k)| (k.id, idx)).collect();
+        let grammemes = dict.grammemes.iter().enumerate().map(|(idx, g)| (g.name.clone(), idx)).collect();
+        let aliases = dict.grammemes.iter().enumerate().map(|(idx, g)| (g.alias.clone(), idx)).collect();
+        Index {
+            forms,
+            lemmata,
+            link_kinds,
+            grammemes,
+            aliases,
+        }
+    }
+}
+
+fn form_hash(word: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    word.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl Dict {
+    /// перестраивает индексы словаря.
+    ///
+    /// Кроме индексов для поиска метод нумерует граммемы в порядке их следования
+    /// в `grammemes` (см. [`Grammeme::index`](struct.Grammeme.html#structfield.index)),
+    /// заменяет граммемы в ограничениях, лексемах и словоформах граммемами словаря
+    /// с теми же именами (см. [`resolve`](#method.resolve)), а лексемы и типы
+    /// в связях — лексемами и типами связей словаря с теми же идентификаторами.
+    /// Уже согласованные со словарём лексемы не копируются.
+    ///
+    /// [`read_from_xml`](#method.read_from_xml) строит индексы сам;
+    /// вызывать этот метод нужно только после изменения лексем, граммем
+    /// или типов связей вручную. До перестройки индексов поиск может
+    /// не находить элементы, позиции которых изменились.
+    ///
+    /// Возвращает ошибку, если граммем больше, чем
+    /// [`GrammemeSet::CAPACITY`](struct.GrammemeSet.html#associatedconstant.CAPACITY),
+    /// или если ограничение, лексема или словоформа ссылается на граммему,
+    /// которой нет в словаре.
+    pub fn reindex(&mut self) -> Result<()> {
+        if self.grammemes.len() > GrammemeSet::CAPACITY {
+            return Err(Error::TooManyGrammemes);
+        }
+        for (index, grammeme) in self.grammemes.iter_mut().enumerate() {
+            if grammeme.index != index {
+                Arc::make_mut(grammeme).index = index;
+            }
+        }
+        self.index = Index::build(self);
+
+        let mut restrictions = std::mem::take(&mut self.restrictions);
+        let result = restrictions.iter_mut().try_for_each(|restriction| {
+            self.resolve_grammeme(&mut restriction.left_grammeme)?;
+            self.resolve_grammeme(&mut restriction.right_grammeme)
+        });
+        self.restrictions = restrictions;
+        result?;
+
+        let mut lemmata = std::mem::take(&mut self.lemmata);
+        let result = lemmata.iter_mut().try_for_each(|lemma| {
+            if self.is_resolved(lemma) {
+                Ok(())
+            } else {
+                self.resolve(Arc::make_mut(lemma))
+            }
+        });
+        self.lemmata = lemmata;
+        result?;
+
+        let mut links = std::mem::take(&mut self.links);
+        for link in &mut links {
+            for lemma in [&mut link.from, &mut link.to] {
+                // связи с лексемами, которых нет в словаре, остаются как есть
+                if let Some(resolved) = self.lemma(lemma.id) {
+                    if !Arc::ptr_eq(lemma, resolved) {
+                        *lemma = resolved.clone();
+                    }
+                }
+            }
+            if let Some(resolved) = self.link_kind(link.kind.id) {
+                if !Arc::ptr_eq(&link.kind, resolved) {
+                    link.kind = resolved.clone();
+                }
+            }
+        }
+        self.links = links;
+        Ok(())
+    }
+
+    /// заменяет граммемы лексемы `lemma` и её словоформ граммемами словаря
+    /// с теми же именами, чтобы их можно было использовать в множествах граммем
+    /// (см. [`Lemma::grammeme_set`](struct.Lemma.html#method.grammeme_set)).
+    ///
+    /// Нужен для лексем, созданных вручную или десериализованных отдельно
+    /// от словаря. Возвращает ошибку, если граммемы с таким именем нет в словаре.
+    pub fn resolve(&self, lemma: &mut Lemma) -> Result<()> {
+        self.resolve_grammemes(&mut lemma.grammemes)?;
+        for form in &mut lemma.forms {
+            self.resolve_grammemes(&mut form.grammemes)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_grammemes(&self, grammemes: &mut [Arc<Grammeme>]) -> Result<()> {
+        for grammeme in grammemes {
+            if self.is_own_grammeme(grammeme) {
+                continue;
+            }
+            match self.grammeme(&grammeme.name) {
+                Some(resolved) => {
+                    if !Arc::ptr_eq(grammeme, resolved) {
+                        *grammeme = resolved.clone();
+                    }
+                }
+                None => return Err(Error::UnknownGrammeme(grammeme.name.clone())),
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_grammeme(&self, grammeme: &mut Option<Arc<Grammeme>>) -> Result<()> {
+        match *grammeme {
+            Some(ref mut grammeme) => self.resolve_grammemes(std::slice::from_mut(grammeme)),
+            None => Ok(()),
+        }
+    }
+
+    /// проверяет, что граммема — граммема словаря; она узнаётся по своей позиции
+    /// без поиска по имени.
+    fn is_own_grammeme(&self, grammeme: &Arc<Grammeme>) -> bool {
+        matches!(self.grammemes.get(grammeme.index), Some(g) if Arc::ptr_eq(g, grammeme))
+    }
+
+    /// проверяет, что лексема и её словоформы ссылаются только на граммемы словаря.
+    fn is_resolved(&self, lemma: &Lemma) -> bool {
+        lemma.grammemes.iter().all(|g| self.is_own_grammeme(g))
+            && lemma.forms.iter().all(|form| form.grammemes.iter().all(|g| self.is_own_grammeme(g)))
+    }
+
+    /// возвращает все пары (лексема, словоформа), у которых текстовое
+    /// представление словоформы совпадает с `word`.
+    ///
+    /// Сравнение точное: словоформы в словаре записаны в нижнем регистре.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use opencorpora::Dict;
+    ///
+    /// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+    /// for (lemma, form) in dict.lookup("стали") {
+    ///     println!("{} -> {} ({} grammemes)", form.word, lemma.word, form.grammemes.len());
+    /// }
+    /// ```
+    pub fn lookup<'a>(&'a self, word: &str) -> impl Iterator<Item = (&'a Lemma, &'a Form)> + 'a {
+        self.form_entries(word)
+            .map(|(lemma, form_idx)| (&**lemma, &lemma.forms[form_idx]))
+    }
+
+    /// возвращает лексемы и номера их словоформ с текстом `word`.
+    pub(crate) fn form_entries<'a>(&'a self, word: &str) -> impl Iterator<Item = (&'a Arc<Lemma>, usize)> + 'a {
+        let positions = match self.index.forms.get(&form_hash(word)) {
+            Some(positions) => positions.as_slice(),
+            None => &[][..],
+        };
+        let form_at = move |&(lemma_idx, form_idx): &(usize, usize)| {
+            let lemma = self.lemmata.get(lemma_idx)?;
+            Some((lemma, form_idx, lemma.forms.get(form_idx)?))
+        };
+        // образец для сравнения берётся из словаря, чтобы итератор не зависел от `word`
+        let found = positions.iter().filter_map(form_at).find(|&(_, _, form)| form.word == word);
+        let (word, positions) = match found {
+            Some((_, _, form)) => (form.word.as_str(), positions),
+            None => ("", &[][..]),
+        };
+        positions
+            .iter()
+            .filter_map(form_at)
+            .filter(move |&(_, _, form)| form.word == word)
+            .map(|(lemma, form_idx, _)| (lemma, form_idx))
+    }
+
+    /// возвращает лексему по идентификатору.
+    pub fn lemma(&self, id: usize) -> Option<&Arc<Lemma>> {
+        let &idx = self.index.lemmata.get(&id)?;
+        self.lemmata.get(idx).filter(|lemma| lemma.id == id)
+    }
+
+    /// возвращает лексему по идентификатору, проверяя, что её ревизия равна `revision`.
+    ///
+    /// Возвращает ошибку, если лексема не найдена или была изменена
+    /// (например, если словарь обновился после того, как вызывающая сторона
+    /// запомнила идентификатор и ревизию).
+    pub fn lemma_with_revision(&self, id: usize, revision: usize) -> Result<&Arc<Lemma>> {
+        let lemma = self.lemma(id).ok_or(Error::UnknownLemma(id))?;
+        if lemma.revision != revision {
+            return Err(Error::RevisionMismatch {
+                id,
+                expected: revision,
+                actual: lemma.revision,
+            });
+        }
+        Ok(lemma)
+    }
+
+    /// возвращает тип связи по идентификатору.
+    pub fn link_kind(&self, id: usize) -> Option<&Arc<LinkKind>> {
+        let &idx = self.index.link_kinds.get(&id)?;
+        self.link_kinds.get(idx).filter(|kind| kind.id == id)
+    }
+
+    /// возвращает граммему по имени на латинице.
+    pub fn grammeme(&self, name: &str) -> Option<&Arc<Grammeme>> {
+        let &idx = self.index.grammemes.get(name)?;
+        self.grammemes.get(idx).filter(|grammeme| grammeme.name == name)
+    }
+
+    /// возвращает граммему по имени на кириллице.
+    pub(crate) fn grammeme_by_alias(&self, alias: &str) -> Option<&Arc<Grammeme>> {
+        let &idx = self.index.aliases.get(alias)?;
+        self.grammemes.get(idx).filter(|grammeme| grammeme.alias == alias)
+    }
+}
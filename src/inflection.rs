@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use crate::dict::{Form, Grammeme, Lemma};
+use crate::grammeme_set::GrammemeSet;
+
+impl Lemma {
+    /// возвращает словоформу лексемы, обладающую всеми граммемами из `grammemes`.
+    ///
+    /// Граммемы лексемы считаются общими для всех словоформ, поэтому запрос
+    /// может содержать и их (например, `NOUN,plur`).
+    /// Если подходящих словоформ несколько, выбирается словоформа с наименьшим
+    /// числом граммем сверх запрошенных, а среди равных — первая по порядку
+    /// в `Lemma::forms`. Если подходящих словоформ нет, возвращается `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use opencorpora::Dict;
+    ///
+    /// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+    /// let wanted = dict.grammeme_set(["datv", "plur"]).unwrap();
+    /// if let Some(form) = dict.lemmata[0].inflect(&wanted) {
+    ///     println!("{}", form.word);
+    /// }
+    /// ```
+    pub fn inflect(&self, grammemes: &GrammemeSet) -> Option<&Form> {
+        let lemma_set = self.grammeme_set();
+        self.forms
+            .iter()
+            .filter_map(|form| {
+                let tag = lemma_set | form.grammeme_set();
+                if tag.is_superset(grammemes) {
+                    Some((tag.len() - grammemes.len(), form))
+                } else {
+                    None
+                }
+            })
+            .min_by_key(|&(extra, _)| extra)
+            .map(|(_, form)| form)
+    }
+
+    /// возвращает словоформу лексемы, согласованную с числительным `num`
+    /// («1 файл», «2 файла», «5 файлов»).
+    ///
+    /// Падеж берётся из словоформы `form`, которая должна принадлежать лексеме.
+    /// В именительном и винительном падежах после 1 (кроме 11) используется
+    /// единственное число, после 2–4 (кроме 12–14) — родительный падеж
+    /// единственного числа, в остальных случаях — родительный падеж
+    /// множественного числа. В косвенных падежах меняется только число.
+    /// Для частей речи, отличных от `NOUN`, `ADJF` и `PRTF`, возвращается `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use opencorpora::Dict;
+    ///
+    /// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+    /// let lemma = &dict.lemmata[0];
+    /// for num in &[1, 2, 5, 11, 21] {
+    ///     if let Some(form) = lemma.make_agree_with_number(&lemma.forms[0], *num) {
+    ///         println!("{} {}", num, form.word);
+    ///     }
+    /// }
+    /// ```
+    pub fn make_agree_with_number(&self, form: &Form, num: u64) -> Option<&Form> {
+        let has = |grammemes: &[Arc<Grammeme>], name: &str| grammemes.iter().any(|g| g.name == name);
+
+        let is_noun = has(&self.grammemes, "NOUN");
+        if !is_noun && !has(&self.grammemes, "ADJF") && !has(&self.grammemes, "PRTF") {
+            return None;
+        }
+
+        let case = form
+            .grammemes
+            .iter()
+            .map(|g| g.name.as_str())
+            .find(|name| CASES.contains(name))
+            .unwrap_or("nomn");
+        let gender = form
+            .grammemes
+            .iter()
+            .map(|g| g.name.as_str())
+            .find(|name| GENDERS.contains(name));
+
+        let number = |agreement: NumeralAgreement| match agreement {
+            NumeralAgreement::One => "sing",
+            _ => "plur",
+        };
+        let mut wanted = match NumeralAgreement::of(num) {
+            // в косвенных падежах числительное согласуется с существительным
+            agreement if case != "nomn" && case != "accs" => vec![number(agreement), case],
+            NumeralAgreement::One => vec!["sing", case],
+            NumeralAgreement::Few
+                if case == "accs" && (has(&self.grammemes, "anim") || has(&form.grammemes, "anim")) =>
+            {
+                vec!["plur", "gent"]
+            }
+            NumeralAgreement::Few if is_noun => vec!["sing", "gent"],
+            NumeralAgreement::Few if gender == Some("femn") => vec!["plur", "nomn"],
+            _ => vec!["plur", "gent"],
+        };
+        if !is_noun && wanted[0] == "sing" {
+            wanted.extend(gender);
+        }
+
+        let mut set = GrammemeSet::new();
+        for name in wanted {
+            // граммемы, которых нет ни в одной словоформе лексемы, заведомо не найдутся
+            set.insert(self.find_grammeme(name)?);
+        }
+        self.inflect(&set)
+    }
+
+    /// ищет граммему по имени среди граммем лексемы и её словоформ.
+    fn find_grammeme(&self, name: &str) -> Option<&Arc<Grammeme>> {
+        self.grammemes
+            .iter()
+            .chain(self.forms.iter().flat_map(|form| &form.grammemes))
+            .find(|g| g.name == name)
+    }
+}
+
+const CASES: &[&str] = &[
+    "nomn", "gent", "datv", "accs", "ablt", "loct", "voct", "gen1", "gen2", "acc2", "loc1", "loc2",
+];
+
+const GENDERS: &[&str] = &["masc", "femn", "neut"];
+
+/// Форма согласования с числительным.
+enum NumeralAgreement {
+    /// 1, 21, 31, … (кроме 11)
+    One,
+
+    /// 2–4, 22–24, … (кроме 12–14)
+    Few,
+
+    /// 0, 5–20, 25–30, …
+    Many,
+}
+
+impl NumeralAgreement {
+    fn of(num: u64) -> NumeralAgreement {
+        let (rem10, rem100) = (num % 10, num % 100);
+        if rem10 == 1 && rem100 != 11 {
+            NumeralAgreement::One
+        } else if (2..=4).contains(&rem10) && !(12..=14).contains(&rem100) {
+            NumeralAgreement::Few
+        } else {
+            NumeralAgreement::Many
+        }
+    }
+}
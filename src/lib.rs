@@ -30,460 +30,130 @@
 //! ```
 
 pub mod error;
+mod analyzer;
+mod binary;
+mod compression;
 mod dict;
-
+mod grammeme_set;
+mod grammeme_tree;
+mod index;
+mod inflection;
+mod link_graph;
+#[cfg(feature = "mmap")]
+mod mapped;
+mod options;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod paradigm;
+mod predictor;
+mod reader;
+#[cfg(feature = "serde")]
+mod serialization;
+mod tag;
+mod validator;
+mod writer;
+
+pub use analyzer::{MorphAnalyzer, Parse};
 pub use dict::{Dict, Form, Grammeme, Lemma, Link, LinkKind, Restriction, RestrictionKind,
                RestrictionScope};
+pub use grammeme_set::GrammemeSet;
+pub use grammeme_tree::GrammemeTree;
+pub use link_graph::{Direction, LinkGraph};
+#[cfg(feature = "mmap")]
+pub use mapped::{MappedDict, MappedForm, MappedForms, MappedGrammemes, MappedLemma, MappedLink};
+pub use options::{LemmaFilter, ParseOptions};
+pub use paradigm::{CompactLemma, Paradigm, ParadigmEntry, ParadigmTable};
+pub use predictor::{Prediction, Predictor};
+pub use reader::{DictReader, Lemmata, LinkRecord, Links, ParsingState};
+pub use tag::{Tag, TagStyle};
+pub use validator::{Validator, Violation, ViolationKind};
 
 use error::{Error, Result};
 
-#[derive(Debug, PartialEq, Eq)]
-enum ParsingState {
-    Start,
-    Dictionary,
-    Grammemes,
-    Grammeme,
-    GrammemeName,
-    GrammemeAlias,
-    GrammemeDescription,
-    Restrictions,
-    Restriction,
-    RestrictionLeft,
-    RestrictionRight,
-    Lemmata,
-    Lemma,
-    LemmaL,
-    LemmaF,
-    LinkTypes,
-    LinkType,
-    Links,
-    End,
-}
-
-fn string_from_bytes(b: &[u8]) -> std::result::Result<String, std::str::Utf8Error> {
-    let s = std::str::from_utf8(b)?;
-    Ok(s.to_owned())
-}
-
-fn integer_from_bytes<N>(b: &[u8]) -> Result<N>
-where
-    N: std::str::FromStr<Err = std::num::ParseIntError>,
-{
-    let s = std::str::from_utf8(b)?;
-    Ok(s.parse()?)
-}
-
-fn get_restriction_scope(el: &quick_xml::events::BytesStart) -> Result<RestrictionScope> {
-    use quick_xml::events::attributes::Attribute;
-
-    for attr in el.attributes() {
-        let Attribute { key: name, value } = attr?;
-        match name {
-            b"type" => match &*value {
-                b"lemma" => {
-                    return Ok(RestrictionScope::Lemma);
-                }
-                b"form" => {
-                    return Ok(RestrictionScope::Form);
-                }
-                _ => {
-                    let s = std::str::from_utf8(&value)?;
-                    let errmsg = format!("invalid restriction scope: '{}'", s);
-                    return Err(Error::Parsing(errmsg));
-                }
-            },
-            _ => (),
-        }
-    }
-    Err(Error::Parsing("restriction scope isn't found".to_owned()))
-}
-
-fn get_grammeme(
-    map: &std::collections::HashMap<String, std::rc::Rc<Grammeme>>,
-    name: &[u8],
-) -> Result<std::rc::Rc<Grammeme>> {
-    let s = string_from_bytes(name)?;
-    match map.get(&s) {
-        Some(grm) => Ok(grm.clone()),
-        None => Err(Error::Parsing(format!("invalid grammeme name: '{}'", s))),
-    }
-}
-
-fn get_lemma(
-    map: &std::collections::HashMap<usize, std::rc::Rc<Lemma>>,
-    id_str: &[u8],
-) -> Result<std::rc::Rc<Lemma>> {
-    let id = integer_from_bytes(id_str)?;
-    match map.get(&id) {
-        Some(lmt) => Ok(lmt.clone()),
-        None => Err(Error::Parsing(format!("invalid lemma id: '{}'", id))),
-    }
-}
-
 impl Dict {
     /// читает словарь из XML-файла.
+    ///
+    /// Для обработки словаря без загрузки всех лексем в память
+    /// можно воспользоваться [`DictReader`](struct.DictReader.html).
     pub fn read_from_xml<R: std::io::Read>(r: R) -> Result<Dict> {
-        use std::io::BufReader;
-        use std::rc::Rc;
-        use std::str;
-        use std::collections::HashMap;
-
-        use quick_xml::Reader;
-        use quick_xml::events::Event;
-        use quick_xml::events::attributes::Attribute;
+        let (dict, _) = Dict::read_from_xml_with_options(r, &ParseOptions::default())?;
+        Ok(dict)
+    }
 
-        let mut state = ParsingState::Start;
+    /// читает словарь из XML-файла с параметрами `options`.
+    ///
+    /// Вместе со словарём возвращается список ошибок, пропущенных
+    /// в нестрогом режиме (см. [`ParseOptions`](struct.ParseOptions.html)),
+    /// упорядоченный по положению в файле. В строгом режиме он всегда пуст.
+    /// Разделы, которые не читаются при параметрах `options`, остаются в словаре пустыми.
+    /// Без раздела лексем связям не на что указывать, поэтому раздел связей
+    /// при этом пропускается без разбора и список связей тоже пуст.
+    pub fn read_from_xml_with_options<R: std::io::Read>(
+        r: R,
+        options: &ParseOptions,
+    ) -> Result<(Dict, Vec<error::ParseError>)> {
+        use std::io::BufReader;
+        use std::sync::Arc;
 
-        let mut dict = Dict::default();
+        let mut reader_options = options.clone();
+        reader_options.links &= options.lemmata;
+        let mut reader = DictReader::with_options(BufReader::new(r), reader_options)?;
 
-        let mut current_grammeme = Grammeme::default();
-        let mut current_restriction = Restriction::default();
-        let mut current_lemma = Lemma::default();
-        let mut current_form = Form::default();
-        let mut current_link_kind = LinkKind::default();
+        let mut dict = Dict {
+            version: reader.version().to_owned(),
+            revision: reader.revision(),
+            ..Dict::default()
+        };
 
-        let mut grammeme_by_name = HashMap::<String, Rc<Grammeme>>::new();
-        let mut lemma_by_id = HashMap::<usize, Rc<Lemma>>::new();
+        dict.grammemes = reader.grammemes()?.to_vec();
+        dict.restrictions = reader.restrictions()?.to_vec();
 
-        let mut reader = Reader::from_reader(BufReader::new(r));
-        let mut buf = Vec::new();
+        for lemma in reader.lemmata() {
+            dict.lemmata.push(Arc::new(lemma?));
+        }
+        Dict::read_links_from_xml(dict, reader, options)
+    }
 
-        loop {
-            match reader.read_event(&mut buf) {
-                Ok(Event::Empty(ref el)) => match el.name() {
-                    b"g" if state == ParsingState::LemmaL => for attr in el.attributes() {
-                        let Attribute { key: name, value } = attr?;
-                        match name {
-                            b"v" => {
-                                let grammeme = get_grammeme(&grammeme_by_name, &value)?;
-                                current_lemma.grammemes.push(grammeme);
-                            }
-                            _ => (),
-                        }
-                    },
-                    b"g" if state == ParsingState::LemmaF => for attr in el.attributes() {
-                        let Attribute { key: name, value } = attr?;
-                        match name {
-                            b"v" => {
-                                let grammeme = get_grammeme(&grammeme_by_name, &value)?;
-                                current_form.grammemes.push(grammeme);
-                            }
-                            _ => (),
-                        }
-                    },
-                    b"link" if state == ParsingState::Links => {
-                        let mut current_link = Link::default();
-                        for attr in el.attributes() {
-                            let Attribute { key: name, value } = attr?;
-                            match name {
-                                b"id" => {
-                                    current_link.id = integer_from_bytes(&value)?;
-                                }
-                                b"from" => {
-                                    let lemma = get_lemma(&lemma_by_id, &value)?;
-                                    current_link.from = lemma;
-                                }
-                                b"to" => {
-                                    let lemma = get_lemma(&lemma_by_id, &value)?;
-                                    current_link.to = lemma;
-                                }
-                                b"type" => {
-                                    let kind_id: usize = integer_from_bytes(&value)?;
-                                    for lk in &dict.link_kinds {
-                                        if kind_id == lk.id {
-                                            current_link.kind = lk.clone();
-                                        }
-                                    }
-                                }
-                                _ => (),
-                            }
-                        }
-                        dict.links.push(current_link.clone());
-                    }
-                    ref name => {
-                        let s = str::from_utf8(name)?;
-                        return Err(Error::Parsing(format!(
-                            "unexpected single tag: '{}'",
-                            s
-                        )));
-                    }
-                },
-                Ok(Event::Start(ref el)) => match el.name() {
-                    b"dictionary" if state == ParsingState::Start => {
-                        state = ParsingState::Dictionary;
-                        for attr in el.attributes() {
-                            let Attribute { key: name, value } = attr?;
-                            match name {
-                                b"version" => {
-                                    dict.version = string_from_bytes(&value)?;
-                                }
-                                b"revision" => {
-                                    dict.revision = integer_from_bytes(&value)?;
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    b"grammemes" if state == ParsingState::Dictionary => {
-                        state = ParsingState::Grammemes;
-                        dict.grammemes.clear();
-                        grammeme_by_name.clear();
-                    }
-                    b"grammeme" if state == ParsingState::Grammemes => {
-                        state = ParsingState::Grammeme;
-                        current_grammeme = Grammeme::default();
-                        for attr in el.attributes() {
-                            let Attribute { key: name, value } = attr?;
-                            match name {
-                                b"parent" => {
-                                    if value.is_empty() {
-                                        current_grammeme.parent = None;
-                                    } else {
-                                        current_grammeme.parent = Some(string_from_bytes(&value)?);
-                                    }
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    b"name" if state == ParsingState::Grammeme => {
-                        state = ParsingState::GrammemeName;
-                    }
-                    b"alias" if state == ParsingState::Grammeme => {
-                        state = ParsingState::GrammemeAlias;
-                    }
-                    b"description" if state == ParsingState::Grammeme => {
-                        state = ParsingState::GrammemeDescription;
-                    }
-                    b"restrictions" if state == ParsingState::Dictionary => {
-                        state = ParsingState::Restrictions;
-                        dict.restrictions.clear();
-                    }
-                    b"restr" if state == ParsingState::Restrictions => {
-                        state = ParsingState::Restriction;
-                        current_restriction = Restriction::default();
-                        for attr in el.attributes() {
-                            let Attribute { key: name, value } = attr?;
-                            match name {
-                                b"type" => match &*value {
-                                    b"maybe" => {
-                                        current_restriction.kind = RestrictionKind::Maybe;
-                                    }
-                                    b"obligatory" => {
-                                        current_restriction.kind = RestrictionKind::Obligatory;
-                                    }
-                                    b"forbidden" => {
-                                        current_restriction.kind = RestrictionKind::Forbidden;
-                                    }
-                                    _ => {
-                                        let s = str::from_utf8(&value)?;
-                                        let errmsg = format!("invalid restriction kind: '{}'", s);
-                                        return Err(Error::Parsing(errmsg));
-                                    }
-                                },
-                                b"auto" => {
-                                    current_restriction.auto = integer_from_bytes(&value)?;
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    b"left" if state == ParsingState::Restriction => {
-                        state = ParsingState::RestrictionLeft;
-                        current_restriction.left_scope = get_restriction_scope(el)?;
-                    }
-                    b"right" if state == ParsingState::Restriction => {
-                        state = ParsingState::RestrictionRight;
-                        current_restriction.right_scope = get_restriction_scope(el)?;
-                    }
-                    b"lemmata" if state == ParsingState::Dictionary => {
-                        state = ParsingState::Lemmata;
-                        dict.lemmata.clear();
-                    }
-                    b"lemma" if state == ParsingState::Lemmata => {
-                        state = ParsingState::Lemma;
-                        current_lemma = Lemma::default();
-                        current_lemma.forms.clear();
-                        for attr in el.attributes() {
-                            let Attribute { key: name, value } = attr?;
-                            match name {
-                                b"id" => {
-                                    current_lemma.id = integer_from_bytes(&value)?;
-                                }
-                                b"rev" => {
-                                    current_lemma.revision = integer_from_bytes(&value)?;
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    b"l" if state == ParsingState::Lemma => {
-                        state = ParsingState::LemmaL;
-                        current_lemma.grammemes.clear();
-                        for attr in el.attributes() {
-                            let Attribute { key: name, value } = attr?;
-                            match name {
-                                b"t" => {
-                                    current_lemma.word = string_from_bytes(&value)?;
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    b"f" if state == ParsingState::Lemma => {
-                        state = ParsingState::LemmaF;
-                        current_form = Form::default();
-                        current_form.grammemes.clear();
-                        for attr in el.attributes() {
-                            let Attribute { key: name, value } = attr?;
-                            match name {
-                                b"t" => {
-                                    current_form.word = string_from_bytes(&value)?;
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    b"link_types" if state == ParsingState::Dictionary => {
-                        state = ParsingState::LinkTypes;
-                        dict.link_kinds.clear();
-                    }
-                    b"type" if state == ParsingState::LinkTypes => {
-                        state = ParsingState::LinkType;
-                        current_link_kind = LinkKind::default();
-                        for attr in el.attributes() {
-                            let Attribute { key: name, value } = attr?;
-                            match name {
-                                b"id" => {
-                                    current_link_kind.id = integer_from_bytes(&value)?;
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    b"links" if state == ParsingState::Dictionary => {
-                        state = ParsingState::Links;
-                        dict.links.clear();
-                    }
-                    ref name => {
-                        let s = str::from_utf8(name)?;
-                        return Err(Error::Parsing(format!(
-                            "unexpected opening tag: '{}'",
-                            s
-                        )));
-                    }
-                },
-                Ok(Event::Text(ref el)) => match state {
-                    ParsingState::GrammemeName => {
-                        current_grammeme.name = string_from_bytes(&el.unescaped()?)?;
-                    }
-                    ParsingState::GrammemeAlias => {
-                        current_grammeme.alias = string_from_bytes(&el.unescaped()?)?;
-                    }
-                    ParsingState::GrammemeDescription => {
-                        current_grammeme.description = string_from_bytes(&el.unescaped()?)?;
-                    }
-                    ParsingState::RestrictionLeft => {
-                        if el.len() > 0 {
-                            current_restriction.left_grammeme =
-                                Some(get_grammeme(&grammeme_by_name, &el.unescaped()?)?);
-                        } else {
-                            current_restriction.left_grammeme = None;
-                        }
-                    }
-                    ParsingState::RestrictionRight => {
-                        if el.len() > 0 {
-                            current_restriction.right_grammeme =
-                                Some(get_grammeme(&grammeme_by_name, &el.unescaped()?)?);
-                        } else {
-                            current_restriction.right_grammeme = None;
-                        }
-                    }
-                    ParsingState::LinkType => {
-                        current_link_kind.name = string_from_bytes(&el.unescaped()?)?;
-                    }
-                    _ => (),
-                },
-                Ok(Event::End(ref el)) => match el.name() {
-                    b"dictionary" if state == ParsingState::Dictionary => {
-                        state = ParsingState::End;
-                    }
-                    b"grammemes" if state == ParsingState::Grammemes => {
-                        state = ParsingState::Dictionary;
-                    }
-                    b"grammeme" if state == ParsingState::Grammeme => {
-                        state = ParsingState::Grammemes;
-                        let last_grammeme = Rc::new(current_grammeme.clone());
-                        dict.grammemes.push(last_grammeme.clone());
-                        grammeme_by_name.insert(last_grammeme.name.clone(), last_grammeme.clone());
-                    }
-                    b"name" if state == ParsingState::GrammemeName => {
-                        state = ParsingState::Grammeme;
-                    }
-                    b"alias" if state == ParsingState::GrammemeAlias => {
-                        state = ParsingState::Grammeme;
-                    }
-                    b"description" if state == ParsingState::GrammemeDescription => {
-                        state = ParsingState::Grammeme;
-                    }
-                    b"restrictions" if state == ParsingState::Restrictions => {
-                        state = ParsingState::Dictionary;
-                    }
-                    b"restr" if state == ParsingState::Restriction => {
-                        state = ParsingState::Restrictions;
-                        dict.restrictions.push(current_restriction.clone());
-                    }
-                    b"left" if state == ParsingState::RestrictionLeft => {
-                        state = ParsingState::Restriction;
-                    }
-                    b"right" if state == ParsingState::RestrictionRight => {
-                        state = ParsingState::Restriction;
-                    }
-                    b"lemmata" if state == ParsingState::Lemmata => {
-                        state = ParsingState::Dictionary;
-                    }
-                    b"lemma" if state == ParsingState::Lemma => {
-                        state = ParsingState::Lemmata;
-                        let last_lemma = Rc::new(current_lemma.clone());
-                        dict.lemmata.push(last_lemma.clone());
-                        lemma_by_id.insert(last_lemma.id, last_lemma.clone());
-                    }
-                    b"l" if state == ParsingState::LemmaL => {
-                        state = ParsingState::Lemma;
-                    }
-                    b"f" if state == ParsingState::LemmaF => {
-                        state = ParsingState::Lemma;
-                        current_lemma.forms.push(current_form.clone());
-                    }
-                    b"link_types" if state == ParsingState::LinkTypes => {
-                        state = ParsingState::Dictionary;
-                    }
-                    b"type" if state == ParsingState::LinkType => {
-                        state = ParsingState::LinkTypes;
-                        dict.link_kinds.push(Rc::new(current_link_kind.clone()));
-                    }
-                    b"links" if state == ParsingState::Links => {
-                        state = ParsingState::Dictionary;
-                    }
-                    ref name => {
-                        let s = str::from_utf8(name)?;
-                        return Err(Error::Parsing(format!(
-                            "unexpected closing tag: '{}'",
-                            s
-                        )));
-                    }
+    /// дочитывает типы связей и связи из XML-файла в словарь `dict`,
+    /// лексемы которого уже прочитаны.
+    pub(crate) fn read_links_from_xml<R: std::io::BufRead>(
+        mut dict: Dict,
+        mut reader: DictReader<R>,
+        options: &ParseOptions,
+    ) -> Result<(Dict, Vec<error::ParseError>)> {
+        dict.link_kinds = reader.link_kinds()?.to_vec();
+
+        // индексы не зависят от связей, поэтому строятся до их чтения
+        dict.reindex()?;
+        let get_lemma = |id: usize| dict.lemma(id).cloned().ok_or(Error::UnknownLemma(id));
+        let mut links = Vec::new();
+        let mut dangling = Vec::new();
+        let mut records = reader.links();
+        while let Some(link) = records.next() {
+            let link = link?;
+            let (from, to) = match (get_lemma(link.from), get_lemma(link.to)) {
+                (Ok(from), Ok(to)) => (from, to),
+                (Err(e), _) | (_, Err(e)) => match records.locate(e) {
+                    Error::Located(diagnostic) if !options.strict => {
+                        dangling.push(*diagnostic);
+                        continue;
+                    }
+                    e => return Err(e),
                 },
-                Err(e) => return Err(e.into()),
-                Ok(Event::Eof) => break,
-                Ok(Event::Decl(_)) => (),
-                e => panic!("!!! {:?}", e),
-            }
+            };
+            links.push(Link {
+                id: link.id,
+                from,
+                to,
+                kind: link.kind,
+            });
         }
+        dict.links = links;
 
-        if state != ParsingState::End {
-            Err(Error::Parsing(format!("invalid state after parsing: {:?}", state)))
-        } else {
-            Ok(dict)
-        }
+        let mut diagnostics = reader.take_diagnostics();
+        reader.finish()?;
+        diagnostics.extend(dangling);
+        diagnostics.sort_by_key(|diagnostic| diagnostic.offset);
+        Ok((dict, diagnostics))
     }
 }
@@ -0,0 +1,127 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use crate::dict::{Dict, Lemma, Link, LinkKind};
+
+/// Направление обхода связей.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// От исходной лексемы связи к конечной
+    Outgoing,
+
+    /// От конечной лексемы связи к исходной
+    Incoming,
+}
+
+/// Индекс связей между лексемами по идентификаторам лексем.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::{Dict, Direction};
+///
+/// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+/// let graph = dict.link_graph();
+/// // причастия и деепричастия, образованные от инфинитива
+/// let derived = graph.reachable(3, Direction::Outgoing, |kind| {
+///     kind.name == "INFN-PRTF" || kind.name == "INFN-GRND"
+/// });
+/// for lemma in derived {
+///     println!("{}", lemma.word);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LinkGraph<'a> {
+    links: &'a [Link],
+    outgoing: HashMap<usize, Vec<usize>>,
+    incoming: HashMap<usize, Vec<usize>>,
+}
+
+impl<'a> LinkGraph<'a> {
+    /// строит индекс по связям словаря `dict`.
+    pub fn new(dict: &'a Dict) -> Self {
+        let mut outgoing = HashMap::<usize, Vec<usize>>::new();
+        let mut incoming = HashMap::<usize, Vec<usize>>::new();
+        for (idx, link) in dict.links.iter().enumerate() {
+            outgoing.entry(link.from.id).or_default().push(idx);
+            incoming.entry(link.to.id).or_default().push(idx);
+        }
+        LinkGraph {
+            links: &dict.links,
+            outgoing,
+            incoming,
+        }
+    }
+
+    /// возвращает связи, исходящие из лексемы с идентификатором `id`.
+    pub fn outgoing(&self, id: usize) -> impl Iterator<Item = &'a Link> + '_ {
+        self.links_of(Direction::Outgoing, id)
+    }
+
+    /// возвращает связи, входящие в лексему с идентификатором `id`.
+    pub fn incoming(&self, id: usize) -> impl Iterator<Item = &'a Link> + '_ {
+        self.links_of(Direction::Incoming, id)
+    }
+
+    /// возвращает связи типа `kind`, исходящие из лексемы с идентификатором `id`.
+    pub fn outgoing_of_kind<'k>(&'k self, id: usize, kind: &'k LinkKind) -> impl Iterator<Item = &'a Link> + 'k {
+        self.outgoing(id).filter(move |link| link.kind.id == kind.id)
+    }
+
+    /// возвращает связи типа `kind`, входящие в лексему с идентификатором `id`.
+    pub fn incoming_of_kind<'k>(&'k self, id: usize, kind: &'k LinkKind) -> impl Iterator<Item = &'a Link> + 'k {
+        self.incoming(id).filter(move |link| link.kind.id == kind.id)
+    }
+
+    /// возвращает лексемы, достижимые из лексемы с идентификатором `id`
+    /// по связям в направлении `direction`, тип которых удовлетворяет `filter`.
+    ///
+    /// Лексемы возвращаются в порядке обхода в ширину, каждая по одному разу;
+    /// исходная лексема в результат не входит.
+    pub fn reachable<F>(&self, id: usize, direction: Direction, filter: F) -> Vec<&'a Arc<Lemma>>
+    where
+        F: Fn(&LinkKind) -> bool,
+    {
+        let mut visited = HashSet::new();
+        visited.insert(id);
+        let mut queue = VecDeque::new();
+        queue.push_back(id);
+        let mut result = Vec::new();
+        while let Some(current) = queue.pop_front() {
+            for link in self.links_of(direction, current) {
+                if !filter(&link.kind) {
+                    continue;
+                }
+                let lemma = match direction {
+                    Direction::Outgoing => &link.to,
+                    Direction::Incoming => &link.from,
+                };
+                if visited.insert(lemma.id) {
+                    queue.push_back(lemma.id);
+                    result.push(lemma);
+                }
+            }
+        }
+        result
+    }
+
+    fn links_of(&self, direction: Direction, id: usize) -> impl Iterator<Item = &'a Link> + '_ {
+        let links = self.links;
+        let map = match direction {
+            Direction::Outgoing => &self.outgoing,
+            Direction::Incoming => &self.incoming,
+        };
+        let indices = match map.get(&id) {
+            Some(indices) => indices.as_slice(),
+            None => &[],
+        };
+        indices.iter().map(move |&idx| &links[idx])
+    }
+}
+
+impl Dict {
+    /// строит индекс связей между лексемами.
+    pub fn link_graph(&self) -> LinkGraph<'_> {
+        LinkGraph::new(self)
+    }
+}
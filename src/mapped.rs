@@ -0,0 +1,370 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use memmap2::Mmap;
+
+use crate::binary::{decode_header, payload, unverified_payload, Decoder, Header, HEADER_LEN};
+use crate::dict::{Grammeme, Lemma, LinkKind, Restriction};
+use crate::error::{Error, Result};
+use crate::grammeme_set::GrammemeSet;
+
+/// Словарь в бинарном формате, отображённый в память.
+///
+/// Читает файл, сохранённый методом [`Dict::save_binary`](struct.Dict.html#method.save_binary),
+/// без десериализации лексем: текстовые представления и наборы граммем читаются
+/// прямо из отображённого файла, поэтому несколько процессов, открывших один файл,
+/// разделяют одни и те же страницы памяти. В память загружаются только граммемы,
+/// ограничения и типы связей.
+///
+/// При открытии проверяются только заголовок и длина файла: проверка контрольной
+/// суммы прочитала бы файл целиком. Её можно выполнить отдельно методом
+/// [`verify`](#method.verify).
+///
+/// Доступно при включённой опции `mmap`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::MappedDict;
+///
+/// let dict = MappedDict::open("dict.bin").unwrap();
+/// for lemma in dict.lemmata() {
+///     let lemma = lemma.unwrap();
+///     for form in lemma.forms() {
+///         println!("{} -> {}", form.word(), lemma.word());
+///     }
+/// }
+/// ```
+pub struct MappedDict {
+    mmap: Mmap,
+    len: usize,
+    header: Header,
+}
+
+impl MappedDict {
+    /// отображает в память файл словаря и проверяет его заголовок.
+    ///
+    /// Файл не должен изменяться, пока словарь открыт. Повреждения данных
+    /// обнаруживаются при обращении к испорченным записям или методом
+    /// [`verify`](#method.verify).
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<MappedDict> {
+        let file = File::open(path)?;
+        // Безопасность: файл открыт только на чтение; изменение файла другим
+        // процессом во время работы не поддерживается (см. документацию метода).
+        let mmap = unsafe { Mmap::map(&file)? };
+        let (len, header) = {
+            let data = unverified_payload(&mmap)?;
+            (data.len(), decode_header(data)?)
+        };
+        Ok(MappedDict { mmap, len, header })
+    }
+
+    /// проверяет контрольную сумму словаря.
+    ///
+    /// Читает файл целиком, поэтому все его страницы загружаются в память.
+    pub fn verify(&self) -> Result<()> {
+        payload(&self.mmap)?;
+        Ok(())
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.mmap[HEADER_LEN..HEADER_LEN + self.len]
+    }
+
+    /// возвращает версию словаря.
+    pub fn version(&self) -> &str {
+        &self.header.version
+    }
+
+    /// возвращает номер ревизии словаря.
+    pub fn revision(&self) -> usize {
+        self.header.revision
+    }
+
+    /// возвращает все граммемы.
+    pub fn grammemes(&self) -> &[Arc<Grammeme>] {
+        &self.header.grammemes
+    }
+
+    /// возвращает все ограничения на использование граммем.
+    pub fn restrictions(&self) -> &[Restriction] {
+        &self.header.restrictions
+    }
+
+    /// возвращает все типы связей.
+    pub fn link_kinds(&self) -> &[Arc<LinkKind>] {
+        &self.header.link_kinds
+    }
+
+    /// возвращает количество лексем.
+    pub fn lemmata_len(&self) -> usize {
+        self.header.lemmata_count
+    }
+
+    /// возвращает лексему с порядковым номером `idx`.
+    pub fn lemma(&self, idx: usize) -> Result<MappedLemma<'_>> {
+        if idx >= self.header.lemmata_count {
+            return Err(Error::Binary(format!("lemma index out of range: {}", idx)));
+        }
+        let range = self.header.lemma_range(self.data(), idx)?;
+        MappedLemma::new(self, &self.data()[range])
+    }
+
+    /// возвращает итератор по всем лексемам.
+    pub fn lemmata(&self) -> impl Iterator<Item = Result<MappedLemma<'_>>> {
+        (0..self.lemmata_len()).map(move |idx| self.lemma(idx))
+    }
+
+    /// возвращает количество связей.
+    pub fn links_len(&self) -> usize {
+        self.header.links_count
+    }
+
+    /// возвращает связь с порядковым номером `idx`.
+    pub fn link(&self, idx: usize) -> Result<MappedLink<'_>> {
+        if idx >= self.header.links_count {
+            return Err(Error::Binary(format!("link index out of range: {}", idx)));
+        }
+        let (id, from, to, kind) = self.header.decode_link(self.data(), idx)?;
+        Ok(MappedLink {
+            dict: self,
+            id,
+            from,
+            to,
+            kind,
+        })
+    }
+
+    /// возвращает итератор по всем связям.
+    pub fn links(&self) -> impl Iterator<Item = Result<MappedLink<'_>>> {
+        (0..self.links_len()).map(move |idx| self.link(idx))
+    }
+}
+
+/// проверяет, что все номера граммем в списке корректны.
+fn check_grammemes(header: &Header, grammemes: &[u8]) -> Result<()> {
+    for idx in grammemes.chunks_exact(4) {
+        let idx = u32::from_le_bytes([idx[0], idx[1], idx[2], idx[3]]) as usize;
+        if idx >= header.grammemes.len() {
+            return Err(Error::Binary(format!("invalid grammeme index: {}", idx)));
+        }
+    }
+    Ok(())
+}
+
+/// строит множество граммем по списку их номеров, уже проверенному `check_grammemes`.
+fn grammeme_set(grammemes: &[u8]) -> GrammemeSet {
+    let mut set = GrammemeSet::new();
+    for idx in grammemes.chunks_exact(4) {
+        set.insert_index(u32::from_le_bytes([idx[0], idx[1], idx[2], idx[3]]) as usize);
+    }
+    set
+}
+
+/// читает список граммем и возвращает его без разбора.
+fn grammemes_ref<'a>(dec: &mut Decoder<'a>) -> Result<&'a [u8]> {
+    let count = dec.u32()? as usize;
+    dec.bytes(4 * count)
+}
+
+/// Итератор по граммемам лексемы или словоформы отображённого словаря.
+pub struct MappedGrammemes<'a> {
+    grammemes: &'a [Arc<Grammeme>],
+    indices: std::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for MappedGrammemes<'a> {
+    type Item = &'a Arc<Grammeme>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.indices.next()?;
+        let idx = u32::from_le_bytes([idx[0], idx[1], idx[2], idx[3]]) as usize;
+        self.grammemes.get(idx)
+    }
+}
+
+/// Лексема отображённого словаря.
+#[derive(Clone, Copy)]
+pub struct MappedLemma<'a> {
+    dict: &'a MappedDict,
+    record: &'a [u8],
+    id: usize,
+    revision: usize,
+    word: &'a str,
+    grammemes: &'a [u8],
+    forms_count: usize,
+    forms: &'a [u8],
+}
+
+impl<'a> MappedLemma<'a> {
+    fn new(dict: &'a MappedDict, record: &'a [u8]) -> Result<MappedLemma<'a>> {
+        let mut dec = Decoder { data: record, pos: 0 };
+        let id = dec.usize()?;
+        let revision = dec.usize()?;
+        let word = dec.str_ref()?;
+        let grammemes = grammemes_ref(&mut dec)?;
+        check_grammemes(&dict.header, grammemes)?;
+        let forms_count = dec.u32()? as usize;
+        let forms = &record[dec.pos..];
+        for _ in 0..forms_count {
+            dec.str_ref()?;
+            check_grammemes(&dict.header, grammemes_ref(&mut dec)?)?;
+        }
+        if dec.pos != record.len() {
+            return Err(Error::Binary(format!("invalid lemma record: {}", id)));
+        }
+        Ok(MappedLemma {
+            dict,
+            record,
+            id,
+            revision,
+            word,
+            grammemes,
+            forms_count,
+            forms,
+        })
+    }
+
+    /// возвращает числовой идентификатор лексемы.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// возвращает номер ревизии лексемы.
+    pub fn revision(&self) -> usize {
+        self.revision
+    }
+
+    /// возвращает текстовое представление исходной словоформы лексемы.
+    pub fn word(&self) -> &'a str {
+        self.word
+    }
+
+    /// возвращает граммемы, описывающие лексему.
+    pub fn grammemes(&self) -> MappedGrammemes<'a> {
+        MappedGrammemes {
+            grammemes: &self.dict.header.grammemes,
+            indices: self.grammemes.chunks_exact(4),
+        }
+    }
+
+    /// возвращает множество граммем лексемы
+    /// (см. [`Lemma::grammeme_set`](struct.Lemma.html#method.grammeme_set)).
+    pub fn grammeme_set(&self) -> GrammemeSet {
+        grammeme_set(self.grammemes)
+    }
+
+    /// возвращает количество словоформ лексемы.
+    pub fn forms_len(&self) -> usize {
+        self.forms_count
+    }
+
+    /// возвращает итератор по словоформам лексемы.
+    pub fn forms(&self) -> MappedForms<'a> {
+        MappedForms {
+            grammemes: &self.dict.header.grammemes,
+            dec: Decoder {
+                data: self.forms,
+                pos: 0,
+            },
+            remaining: self.forms_count,
+        }
+    }
+
+    /// копирует лексему в обычную структуру [`Lemma`](struct.Lemma.html).
+    pub fn to_lemma(&self) -> Result<Lemma> {
+        self.dict.header.decode_lemma(self.record)
+    }
+}
+
+/// Итератор по словоформам лексемы отображённого словаря.
+pub struct MappedForms<'a> {
+    grammemes: &'a [Arc<Grammeme>],
+    dec: Decoder<'a>,
+    remaining: usize,
+}
+
+impl<'a> Iterator for MappedForms<'a> {
+    type Item = MappedForm<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        // запись лексемы проверена при создании `MappedLemma`, поэтому ошибок здесь нет
+        let word = self.dec.str_ref().ok()?;
+        let grammemes = grammemes_ref(&mut self.dec).ok()?;
+        Some(MappedForm {
+            grammemes: self.grammemes,
+            word,
+            indices: grammemes,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Словоформа лексемы отображённого словаря.
+#[derive(Clone, Copy)]
+pub struct MappedForm<'a> {
+    grammemes: &'a [Arc<Grammeme>],
+    word: &'a str,
+    indices: &'a [u8],
+}
+
+impl<'a> MappedForm<'a> {
+    /// возвращает текстовое представление словоформы.
+    pub fn word(&self) -> &'a str {
+        self.word
+    }
+
+    /// возвращает граммемы, описывающие словоформу.
+    pub fn grammemes(&self) -> MappedGrammemes<'a> {
+        MappedGrammemes {
+            grammemes: self.grammemes,
+            indices: self.indices.chunks_exact(4),
+        }
+    }
+
+    /// возвращает множество граммем словоформы
+    /// (см. [`Form::grammeme_set`](struct.Form.html#method.grammeme_set)).
+    pub fn grammeme_set(&self) -> GrammemeSet {
+        grammeme_set(self.indices)
+    }
+}
+
+/// Связь между лексемами отображённого словаря.
+#[derive(Clone)]
+pub struct MappedLink<'a> {
+    dict: &'a MappedDict,
+    id: usize,
+    from: usize,
+    to: usize,
+    kind: Arc<LinkKind>,
+}
+
+impl<'a> MappedLink<'a> {
+    /// возвращает числовой идентификатор связи.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// возвращает лексему с исходной стороны связи.
+    pub fn from(&self) -> Result<MappedLemma<'a>> {
+        self.dict.lemma(self.from)
+    }
+
+    /// возвращает лексему с конечной стороны связи.
+    pub fn to(&self) -> Result<MappedLemma<'a>> {
+        self.dict.lemma(self.to)
+    }
+
+    /// возвращает тип связи.
+    pub fn kind(&self) -> &Arc<LinkKind> {
+        &self.kind
+    }
+}
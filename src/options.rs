@@ -0,0 +1,136 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::dict::Lemma;
+
+/// Условие отбора лексем при чтении словаря
+/// (см. [`ParseOptions::lemma_filter`](struct.ParseOptions.html#structfield.lemma_filter)).
+pub type LemmaFilter = Arc<dyn Fn(&Lemma) -> bool + Send + Sync>;
+
+/// Параметры чтения XML-словаря.
+///
+/// В следующих версиях могут появиться новые параметры, поэтому вне библиотеки
+/// структуру нельзя создать литералом: параметры создаются методом
+/// `ParseOptions::default()` и изменяются присваиванием полей.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::{Dict, ParseOptions};
+///
+/// let mut options = ParseOptions::default();
+/// options.strict = false;
+/// let file = std::fs::File::open("dict.xml").unwrap();
+/// let (dict, diagnostics) = Dict::read_from_xml_with_options(file, &options).unwrap();
+/// for diagnostic in &diagnostics {
+///     println!("skipped: {}", diagnostic);
+/// }
+/// println!("Lemmata count: {}", dict.lemmata.len());
+/// ```
+///
+/// Ненужные разделы можно пропустить: их содержимое не разбирается,
+/// а ошибки в нём не обнаруживаются.
+///
+/// ```no_run
+/// use opencorpora::{Dict, ParseOptions};
+///
+/// let mut options = ParseOptions::default();
+/// options.restrictions = false;
+/// options.link_types = false;
+/// options.links = false;
+/// let file = std::fs::File::open("dict.xml").unwrap();
+/// let (dict, _) = Dict::read_from_xml_with_options(file, &options).unwrap();
+/// assert!(dict.links.is_empty());
+/// ```
+///
+/// Лексемы можно отбирать по заголовку и граммемам:
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use opencorpora::{Dict, ParseOptions};
+///
+/// let mut options = ParseOptions::default();
+/// options.lemma_filter = Some(Arc::new(|lemma| lemma.grammemes.iter().any(|g| g.name == "NOUN")));
+/// let file = std::fs::File::open("dict.xml").unwrap();
+/// let (nouns, _) = Dict::read_from_xml_with_options(file, &options).unwrap();
+/// println!("Nouns count: {}", nouns.lemmata.len());
+/// ```
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Строгий режим: любая ошибка прерывает чтение словаря.
+    ///
+    /// В нестрогом режиме некорректные лексемы, ограничения и связи (в том числе
+    /// связи с несуществующими лексемами и типами связей) пропускаются, а ошибки
+    /// собираются в список диагностических сообщений. Ошибки в структуре XML-файла
+    /// и в разделах граммем и типов связей прерывают чтение в любом режиме.
+    pub strict: bool,
+
+    /// Читать раздел граммем.
+    ///
+    /// Граммемы читаются независимо от этого параметра, если читаются
+    /// ограничения или лексемы: без граммем их нельзя разобрать.
+    pub grammemes: bool,
+
+    /// Читать раздел ограничений.
+    pub restrictions: bool,
+
+    /// Читать раздел лексем.
+    ///
+    /// Если лексемы не читаются, `Dict::read_from_xml_with_options` пропускает
+    /// раздел связей без разбора, а `Dict::links` остаётся пустым. Связи с идентификаторами
+    /// лексем можно прочитать читателем [`DictReader`](struct.DictReader.html)
+    /// (см. [`DictReader::links`](struct.DictReader.html#method.links)).
+    pub lemmata: bool,
+
+    /// Читать раздел типов связей.
+    ///
+    /// Типы связей читаются независимо от этого параметра, если читаются связи:
+    /// при чтении в [`Dict`](struct.Dict.html) — только вместе с лексемами.
+    pub link_types: bool,
+
+    /// Читать раздел связей.
+    pub links: bool,
+
+    /// Условие отбора лексем.
+    ///
+    /// Вызывается для каждой лексемы сразу после чтения элемента `l`: у переданной
+    /// лексемы заполнены идентификатор, ревизия, слово и граммемы, но ещё нет форм.
+    /// Отклонённые лексемы пропускаются без разбора форм, а связи с ними —
+    /// без проверки и без сообщений об ошибках.
+    ///
+    /// При чтении [`Dict::read_from_xml_parallel`](struct.Dict.html#method.read_from_xml_parallel)
+    /// условие вызывается из нескольких потоков, а если при разборе произошла ошибка,
+    /// словарь читается заново последовательно и условие вызывается повторно
+    /// для уже проверенных лексем.
+    pub lemma_filter: Option<LemmaFilter>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            strict: true,
+            grammemes: true,
+            restrictions: true,
+            lemmata: true,
+            link_types: true,
+            links: true,
+            lemma_filter: None,
+        }
+    }
+}
+
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("strict", &self.strict)
+            .field("grammemes", &self.grammemes)
+            .field("restrictions", &self.restrictions)
+            .field("lemmata", &self.lemmata)
+            .field("link_types", &self.link_types)
+            .field("links", &self.links)
+            .field("lemma_filter", &self.lemma_filter.as_ref().map(|_| "Fn(&Lemma) -> bool"))
+            .finish()
+    }
+}
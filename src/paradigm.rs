@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::dict::{Dict, Form, Grammeme, Lemma};
+
+/// Элемент парадигмы: словоформа получается как `prefix` + основа + `suffix`.
+#[derive(Clone, Debug)]
+pub struct ParadigmEntry {
+    /// Приставка перед основой
+    pub prefix: String,
+
+    /// Окончание после основы
+    pub suffix: String,
+
+    /// Множество граммем описывающих словоформу
+    pub grammemes: Vec<Arc<Grammeme>>,
+}
+
+/// Парадигма словоизменения, общая для многих лексем.
+#[derive(Clone, Debug)]
+pub struct Paradigm {
+    /// Приставка исходной словоформы лексемы
+    pub lemma_prefix: String,
+
+    /// Окончание исходной словоформы лексемы
+    pub lemma_suffix: String,
+
+    /// Элементы парадигмы в порядке следования словоформ в лексеме
+    pub entries: Vec<ParadigmEntry>,
+}
+
+/// Лексема, разложенная на основу и номер парадигмы.
+#[derive(Clone, Debug)]
+pub struct CompactLemma {
+    /// Числовой идентификатор лексемы
+    pub id: usize,
+
+    /// Номер ревизии
+    pub revision: usize,
+
+    /// Основа, общая для всех словоформ лексемы
+    pub stem: String,
+
+    /// Номер парадигмы в `ParadigmTable::paradigms`
+    pub paradigm: usize,
+
+    /// Множество граммем описывающих лексему
+    pub grammemes: Vec<Arc<Grammeme>>,
+}
+
+/// Таблица парадигм словаря и лексемы, выраженные через неё.
+///
+/// Вместо полного списка словоформ каждая лексема хранит только основу и номер
+/// парадигмы; словоформы восстанавливаются по запросу.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::{Dict, ParadigmTable};
+///
+/// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+/// let table = ParadigmTable::new(&dict);
+/// println!("{} lemmata, {} paradigms", table.lemmata.len(), table.paradigms.len());
+/// for form in table.forms(&table.lemmata[0]) {
+///     println!("{}", form.word);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ParadigmTable {
+    /// Множество различных парадигм
+    pub paradigms: Vec<Paradigm>,
+
+    /// Лексемы в порядке следования в словаре
+    pub lemmata: Vec<CompactLemma>,
+}
+
+/// Ключ для поиска одинаковых парадигм.
+type ParadigmKey = (String, String, Vec<(String, String, Vec<String>)>);
+
+/// находит самую длинную подстроку, входящую во все слова.
+fn longest_common_substring(words: &[&[char]]) -> Vec<char> {
+    let shortest = match words.iter().min_by_key(|w| w.len()) {
+        Some(word) => *word,
+        None => return Vec::new(),
+    };
+    for len in (1..=shortest.len()).rev() {
+        for start in 0..=shortest.len() - len {
+            let candidate = &shortest[start..start + len];
+            if words.iter().all(|w| w.windows(len).any(|window| window == candidate)) {
+                return candidate.to_vec();
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// разбивает слово на приставку и окончание вокруг первого вхождения основы.
+fn split_around(word: &[char], stem: &[char]) -> (String, String) {
+    let pos = if stem.is_empty() {
+        0
+    } else {
+        word.windows(stem.len()).position(|window| window == stem).unwrap_or(0)
+    };
+    let prefix = word[..pos].iter().collect();
+    let suffix = word[pos + stem.len()..].iter().collect();
+    (prefix, suffix)
+}
+
+fn names(grammemes: &[Arc<Grammeme>]) -> Vec<String> {
+    grammemes.iter().map(|g| g.name.clone()).collect()
+}
+
+impl ParadigmTable {
+    /// выделяет парадигмы из всех лексем словаря `dict`.
+    pub fn new(dict: &Dict) -> ParadigmTable {
+        let mut table = ParadigmTable::default();
+        let mut paradigm_by_key = HashMap::<ParadigmKey, usize>::new();
+
+        for lemma in &dict.lemmata {
+            let lemma_word: Vec<char> = lemma.word.chars().collect();
+            let form_words: Vec<Vec<char>> = lemma.forms.iter().map(|f| f.word.chars().collect()).collect();
+
+            let mut words: Vec<&[char]> = form_words.iter().map(|w| w.as_slice()).collect();
+            words.push(&lemma_word);
+            let stem = longest_common_substring(&words);
+
+            let (lemma_prefix, lemma_suffix) = split_around(&lemma_word, &stem);
+            let entries: Vec<ParadigmEntry> = lemma
+                .forms
+                .iter()
+                .zip(&form_words)
+                .map(|(form, word)| {
+                    let (prefix, suffix) = split_around(word, &stem);
+                    ParadigmEntry {
+                        prefix,
+                        suffix,
+                        grammemes: form.grammemes.clone(),
+                    }
+                })
+                .collect();
+
+            let key = (
+                lemma_prefix.clone(),
+                lemma_suffix.clone(),
+                entries
+                    .iter()
+                    .map(|e| (e.prefix.clone(), e.suffix.clone(), names(&e.grammemes)))
+                    .collect(),
+            );
+            let paradigms = &mut table.paradigms;
+            let paradigm = *paradigm_by_key.entry(key).or_insert_with(|| {
+                paradigms.push(Paradigm {
+                    lemma_prefix,
+                    lemma_suffix,
+                    entries,
+                });
+                paradigms.len() - 1
+            });
+
+            table.lemmata.push(CompactLemma {
+                id: lemma.id,
+                revision: lemma.revision,
+                stem: stem.into_iter().collect(),
+                paradigm,
+                grammemes: lemma.grammemes.clone(),
+            });
+        }
+
+        table
+    }
+
+    /// восстанавливает исходную словоформу лексемы.
+    pub fn word(&self, lemma: &CompactLemma) -> String {
+        let paradigm = &self.paradigms[lemma.paradigm];
+        format!("{}{}{}", paradigm.lemma_prefix, lemma.stem, paradigm.lemma_suffix)
+    }
+
+    /// восстанавливает все словоформы лексемы.
+    pub fn forms(&self, lemma: &CompactLemma) -> Vec<Form> {
+        self.paradigms[lemma.paradigm]
+            .entries
+            .iter()
+            .map(|entry| Form {
+                word: format!("{}{}{}", entry.prefix, lemma.stem, entry.suffix),
+                grammemes: entry.grammemes.clone(),
+            })
+            .collect()
+    }
+
+    /// восстанавливает лексему целиком.
+    pub fn lemma(&self, lemma: &CompactLemma) -> Lemma {
+        Lemma {
+            id: lemma.id,
+            revision: lemma.revision,
+            word: self.word(lemma),
+            grammemes: lemma.grammemes.clone(),
+            forms: self.forms(lemma),
+        }
+    }
+}
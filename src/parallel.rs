@@ -0,0 +1,182 @@
+use std::io::Read;
+use std::sync::Arc;
+
+use memchr::memmem;
+use rayon::prelude::*;
+
+use crate::dict::Dict;
+use crate::error::{Error, ParseError, Result};
+use crate::options::ParseOptions;
+use crate::reader::{DictReader, CHUNK_PREFIX, TAIL_PREFIX};
+
+/// Наименьший размер части раздела лексем, разбираемой одним потоком.
+const MIN_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Позиция начала части файла, разбираемой отдельным читателем.
+#[derive(Clone, Copy, Debug)]
+struct Origin {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Origin {
+    /// возвращает позицию, следующую за данными `data`, начинающимися в позиции `self`.
+    ///
+    /// Строки и столбцы считаются так же, как при последовательном чтении:
+    /// столбец — в символах, а не в байтах UTF-8.
+    fn advance(self, data: &[u8]) -> Origin {
+        let chars = |bytes: &[u8]| bytes.iter().filter(|&&b| b & 0xc0 != 0x80).count();
+        match memchr::memrchr(b'\n', data) {
+            Some(last) => Origin {
+                offset: self.offset + data.len(),
+                line: self.line + memchr::memchr_iter(b'\n', data).count(),
+                column: 1 + chars(&data[last + 1..]),
+            },
+            None => Origin {
+                offset: self.offset + data.len(),
+                line: self.line,
+                column: self.column + chars(data),
+            },
+        }
+    }
+
+    /// переводит позицию сообщения из координат части файла, перед которой
+    /// читатель разобрал теги `prefix`, в координаты всего файла.
+    fn shift(self, prefix: &[u8], mut diagnostic: ParseError) -> ParseError {
+        // теги не содержат переводов строк и состоят из символов ASCII
+        if diagnostic.line == 1 {
+            diagnostic.column = diagnostic.column - prefix.len() + self.column - 1;
+        }
+        diagnostic.offset = diagnostic.offset - prefix.len() + self.offset;
+        diagnostic.line += self.line - 1;
+        diagnostic
+    }
+}
+
+/// делит раздел лексем на части размером не меньше `chunk_size`,
+/// каждая из которых, кроме первой, начинается с тега `lemma`.
+fn split_lemmata(lemmata: &[u8], chunk_size: usize) -> Vec<&[u8]> {
+    let finder = memmem::Finder::new(b"<lemma ");
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while lemmata.len() - start > chunk_size {
+        match finder.find(&lemmata[start + chunk_size..]) {
+            Some(pos) => {
+                let end = start + chunk_size + pos;
+                chunks.push(&lemmata[start..end]);
+                start = end;
+            }
+            None => break,
+        }
+    }
+    chunks.push(&lemmata[start..]);
+    chunks
+}
+
+/// дочитывает словарь, разбирая части раздела лексем, начинающегося
+/// со смещения `start`, параллельно.
+///
+/// Позиции диагностических сообщений пересчитываются в координаты всего файла.
+fn read_lemmata_parallel(
+    data: &[u8],
+    start: usize,
+    mut reader: DictReader<&[u8]>,
+    mut dict: Dict,
+    options: &ParseOptions,
+) -> Result<(Dict, Vec<ParseError>)> {
+    let end = match memmem::find(&data[start..], b"</lemmata>") {
+        Some(pos) => start + pos,
+        None => return Err(Error::UnexpectedEof),
+    };
+    let chunk_size = MIN_CHUNK_SIZE.max((end - start) / (rayon::current_num_threads() * 4));
+    let chunks = split_lemmata(&data[start..end], chunk_size);
+    let mut origin = Origin {
+        offset: 0,
+        line: 1,
+        column: 1,
+    }
+    .advance(&data[..start]);
+    let mut origins = Vec::with_capacity(chunks.len());
+    for chunk in &chunks {
+        origins.push(origin);
+        origin = origin.advance(chunk);
+    }
+    let tail_origin = origin;
+
+    let readers = chunks.into_iter().map(|chunk| reader.lemmata_chunk(chunk)).collect::<Result<Vec<_>>>()?;
+    // части собираются в исходном порядке независимо от порядка их разбора
+    let parsed = readers
+        .into_par_iter()
+        .map(|mut chunk| {
+            let lemmata = chunk.lemmata().map(|lemma| lemma.map(Arc::new)).collect::<Result<Vec<_>>>()?;
+            Ok((lemmata, chunk.take_rejected_lemmata(), chunk.take_diagnostics()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut diagnostics = reader.take_diagnostics();
+    let mut tail = reader.lemmata_tail(&data[end..])?;
+    for ((lemmata, rejected, chunk_diagnostics), origin) in parsed.into_iter().zip(origins) {
+        dict.lemmata.extend(lemmata);
+        tail.reject_lemmata(rejected);
+        diagnostics.extend(chunk_diagnostics.into_iter().map(|d| origin.shift(CHUNK_PREFIX, d)));
+    }
+    let (dict, tail_diagnostics) = Dict::read_links_from_xml(dict, tail, options)?;
+    diagnostics.extend(tail_diagnostics.into_iter().map(|d| tail_origin.shift(TAIL_PREFIX, d)));
+    diagnostics.sort_by_key(|diagnostic| diagnostic.offset);
+    Ok((dict, diagnostics))
+}
+
+impl Dict {
+    /// читает словарь из XML-файла с параметрами `options`,
+    /// разбирая лексемы в несколько потоков.
+    ///
+    /// Файл читается в память целиком, раздел лексем делится на части по границам
+    /// элементов `lemma`, и части разбираются в пуле потоков
+    /// [rayon](https://docs.rs/rayon). Результат, включая диагностические сообщения
+    /// нестрогого режима, совпадает с результатом
+    /// [`read_from_xml_with_options`](#method.read_from_xml_with_options).
+    /// Доступно с cargo-опцией `parallel`.
+    ///
+    /// Если при разборе лексем или следующих за ними разделов произошла ошибка,
+    /// словарь читается заново последовательно, чтобы вернуть ту же ошибку,
+    /// что и [`read_from_xml_with_options`](#method.read_from_xml_with_options):
+    /// ошибка может быть вызвана делением на части внутри комментария. В этом
+    /// случае файл разбирается дважды, а условие отбора
+    /// [`ParseOptions::lemma_filter`](struct.ParseOptions.html#structfield.lemma_filter)
+    /// вызывается повторно для уже проверенных лексем.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use opencorpora::{Dict, ParseOptions};
+    ///
+    /// let file = std::fs::File::open("dict.xml").unwrap();
+    /// let (dict, _) = Dict::read_from_xml_parallel(file, &ParseOptions::default()).unwrap();
+    /// println!("Lemmata count: {}", dict.lemmata.len());
+    /// ```
+    pub fn read_from_xml_parallel<R: Read>(mut r: R, options: &ParseOptions) -> Result<(Dict, Vec<ParseError>)> {
+        let mut data = Vec::new();
+        r.read_to_end(&mut data)?;
+        if !options.lemmata {
+            return Dict::read_from_xml_with_options(&data[..], options);
+        }
+
+        // до раздела лексем файл читается так же, как при последовательном чтении
+        let mut reader = DictReader::with_options(&data[..], options.clone())?;
+        let dict = Dict {
+            version: reader.version().to_owned(),
+            revision: reader.revision(),
+            grammemes: reader.grammemes()?.to_vec(),
+            restrictions: reader.restrictions()?.to_vec(),
+            ..Dict::default()
+        };
+        match reader.enter_lemmata()? {
+            Some(start) => match read_lemmata_parallel(&data, start, reader, dict, options) {
+                Ok(result) => Ok(result),
+                Err(_) => Dict::read_from_xml_with_options(&data[..], options),
+            },
+            None => Dict::read_from_xml_with_options(&data[..], options),
+        }
+    }
+}
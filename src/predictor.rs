@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::dict::Dict;
+use crate::tag::Tag;
+
+/// Максимальная длина окончания (в символах), по которому делается предсказание.
+const MAX_SUFFIX_LEN: usize = 5;
+
+/// Части речи, которые не пополняются новыми словами и не участвуют в предсказании.
+const UNPRODUCTIVE: &[&str] = &["NUMR", "NPRO", "PRED", "PREP", "CONJ", "PRCL", "INTJ"];
+
+/// Вариант разбора слова, отсутствующего в словаре.
+#[derive(Clone, Debug)]
+pub struct Prediction {
+    /// Предполагаемая нормальная форма слова
+    pub normal_form: String,
+
+    /// Полный тег словоформы: граммемы лексемы и граммемы словоформы
+    pub tag: Tag,
+
+    /// Оценка правдоподобия разбора от 0 до 1
+    pub score: f64,
+}
+
+/// Правило преобразования словоформы в нормальную форму.
+#[derive(Debug)]
+struct Rule {
+    /// Количество символов, отрезаемых от конца словоформы
+    cut: usize,
+
+    /// Окончание нормальной формы, добавляемое после отрезания
+    append: String,
+
+    /// Номер набора граммем в `Predictor::tags`
+    tag: usize,
+
+    /// Количество словоформ словаря, подтверждающих правило
+    count: usize,
+}
+
+/// Предсказатель разборов для слов, отсутствующих в словаре.
+///
+/// Строится по лексемам словаря: для каждой словоформы запоминается, как её
+/// окончание преобразуется в окончание нормальной формы и какие граммемы ей
+/// соответствуют. Неизвестное слово разбирается по самому длинному
+/// из известных окончаний.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::{Dict, Predictor};
+///
+/// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+/// let predictor = Predictor::new(&dict);
+/// for prediction in predictor.predict("бутявками") {
+///     println!("{} {:.2}", prediction.normal_form, prediction.score);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Predictor {
+    tags: Vec<Tag>,
+    rules: HashMap<String, Vec<Rule>>,
+}
+
+impl Predictor {
+    /// строит предсказатель по лексемам словаря `dict`.
+    pub fn new(dict: &Dict) -> Predictor {
+        let mut tags = Vec::new();
+        let mut tag_by_grammemes = HashMap::<(Vec<usize>, Vec<usize>), usize>::new();
+        let mut counts = HashMap::<String, HashMap<(usize, String, usize), usize>>::new();
+
+        for lemma in &dict.lemmata {
+            if lemma.grammemes.iter().any(|g| UNPRODUCTIVE.contains(&g.name.as_str())) {
+                continue;
+            }
+            let lemma_chars: Vec<char> = lemma.word.chars().collect();
+            for form in &lemma.forms {
+                let form_chars: Vec<char> = form.word.chars().collect();
+                let stem_len = form_chars
+                    .iter()
+                    .zip(&lemma_chars)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                let cut = form_chars.len() - stem_len;
+                let append: String = lemma_chars[stem_len..].iter().collect();
+
+                let key = (
+                    lemma.grammemes.iter().map(|g| g.index).collect(),
+                    form.grammemes.iter().map(|g| g.index).collect(),
+                );
+                let tag = *tag_by_grammemes.entry(key).or_insert_with(|| {
+                    tags.push(lemma.tag(form));
+                    tags.len() - 1
+                });
+
+                for suffix_len in cut.max(1)..=MAX_SUFFIX_LEN.min(form_chars.len()) {
+                    let suffix: String = form_chars[form_chars.len() - suffix_len..].iter().collect();
+                    *counts
+                        .entry(suffix)
+                        .or_default()
+                        .entry((cut, append.clone(), tag))
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        let rules = counts
+            .into_iter()
+            .map(|(suffix, rules)| {
+                let mut rules: Vec<Rule> = rules
+                    .into_iter()
+                    .map(|((cut, append, tag), count)| Rule { cut, append, tag, count })
+                    .collect();
+                rules.sort_by(|a, b| {
+                    b.count
+                        .cmp(&a.count)
+                        .then_with(|| a.tag.cmp(&b.tag))
+                        .then_with(|| a.cut.cmp(&b.cut))
+                        .then_with(|| a.append.cmp(&b.append))
+                });
+                (suffix, rules)
+            })
+            .collect();
+
+        Predictor { tags, rules }
+    }
+
+    /// возвращает возможные разборы слова `word`, упорядоченные по убыванию оценки.
+    ///
+    /// Слово приводится к нижнему регистру. Используется самое длинное окончание,
+    /// для которого есть хотя бы одно применимое правило. Если такого окончания нет,
+    /// возвращается пустой список.
+    pub fn predict(&self, word: &str) -> Vec<Prediction> {
+        let chars: Vec<char> = word.to_lowercase().chars().collect();
+        for suffix_len in (1..=MAX_SUFFIX_LEN.min(chars.len())).rev() {
+            let suffix: String = chars[chars.len() - suffix_len..].iter().collect();
+            let rules: Vec<&Rule> = match self.rules.get(&suffix) {
+                // правило не применимо, если отрезает всё слово
+                Some(rules) => rules.iter().filter(|rule| rule.cut < chars.len()).collect(),
+                None => continue,
+            };
+            if rules.is_empty() {
+                continue;
+            }
+            let total: usize = rules.iter().map(|rule| rule.count).sum();
+            return rules
+                .into_iter()
+                .map(|rule| {
+                    let stem: String = chars[..chars.len() - rule.cut].iter().collect();
+                    Prediction {
+                        normal_form: stem + &rule.append,
+                        tag: self.tags[rule.tag].clone(),
+                        score: rule.count as f64 / total as f64,
+                    }
+                })
+                .collect();
+        }
+        Vec::new()
+    }
+}
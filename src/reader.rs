@@ -0,0 +1,1058 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, Read};
+use std::sync::Arc;
+
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::dict::{Form, Grammeme, Lemma, LinkKind, Restriction, RestrictionKind, RestrictionScope};
+use crate::error::{Error, ParseError, Result};
+use crate::grammeme_set::GrammemeSet;
+use crate::grammeme_tree::GrammemeTree;
+use crate::options::ParseOptions;
+
+/// Состояние читателя XML-словаря: элемент, внутри которого он находится.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParsingState {
+    /// До элемента `dictionary`
+    Start,
+    /// Внутри `dictionary`, между разделами
+    Dictionary,
+    /// Внутри `grammemes`
+    Grammemes,
+    /// Внутри `grammeme`
+    Grammeme,
+    /// Внутри `grammeme/name`
+    GrammemeName,
+    /// Внутри `grammeme/alias`
+    GrammemeAlias,
+    /// Внутри `grammeme/description`
+    GrammemeDescription,
+    /// Внутри `restrictions`
+    Restrictions,
+    /// Внутри `restr`
+    Restriction,
+    /// Внутри `restr/left`
+    RestrictionLeft,
+    /// Внутри `restr/right`
+    RestrictionRight,
+    /// Внутри `lemmata`
+    Lemmata,
+    /// Внутри `lemma`
+    Lemma,
+    /// Внутри `lemma/l`
+    LemmaL,
+    /// Внутри `lemma/f`
+    LemmaF,
+    /// Внутри `link_types`
+    LinkTypes,
+    /// Внутри `link_types/type`
+    LinkType,
+    /// Внутри `links`
+    Links,
+    /// После элемента `dictionary`
+    End,
+}
+
+/// Раздел словаря в порядке следования в XML-файле.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    Header,
+    Grammemes,
+    Restrictions,
+    Lemmata,
+    LinkTypes,
+    Links,
+    End,
+}
+
+impl ParsingState {
+    fn section(&self) -> Section {
+        match *self {
+            ParsingState::Start | ParsingState::Dictionary => Section::Header,
+            ParsingState::Grammemes
+            | ParsingState::Grammeme
+            | ParsingState::GrammemeName
+            | ParsingState::GrammemeAlias
+            | ParsingState::GrammemeDescription => Section::Grammemes,
+            ParsingState::Restrictions
+            | ParsingState::Restriction
+            | ParsingState::RestrictionLeft
+            | ParsingState::RestrictionRight => Section::Restrictions,
+            ParsingState::Lemmata
+            | ParsingState::Lemma
+            | ParsingState::LemmaL
+            | ParsingState::LemmaF => Section::Lemmata,
+            ParsingState::LinkTypes | ParsingState::LinkType => Section::LinkTypes,
+            ParsingState::Links => Section::Links,
+            ParsingState::End => Section::End,
+        }
+    }
+}
+
+/// Обёртка над источником данных, считающая строки и столбцы прочитанного текста.
+struct LineCounter<R> {
+    inner: R,
+    consumed: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<R: BufRead> LineCounter<R> {
+    fn new(inner: R) -> Self {
+        LineCounter {
+            inner,
+            consumed: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl<R: BufRead> Read for LineCounter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = {
+            let available = self.fill_buf()?;
+            let n = available.len().min(buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for LineCounter<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Ok(buf) = self.inner.fill_buf() {
+            for &b in &buf[..amt.min(buf.len())] {
+                if b == b'\n' {
+                    self.line += 1;
+                    self.column = 1;
+                } else if b & 0xc0 != 0x80 {
+                    // столбец считается в символах, а не в байтах UTF-8
+                    self.column += 1;
+                }
+            }
+        }
+        self.consumed += amt;
+        self.inner.consume(amt);
+    }
+}
+
+/// Позиция начала XML-события.
+#[derive(Clone, Copy, Debug, Default)]
+struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+/// Результат обработки одного XML-события.
+enum Step {
+    Continue,
+    Lemma(Lemma),
+    Link(LinkRecord),
+    Eof,
+}
+
+/// Связь между лексемами в том виде, в котором она записана в XML-файле.
+///
+/// В отличие от [`Link`](struct.Link.html) лексемы указываются идентификаторами,
+/// так как при потоковом чтении сами лексемы уже не хранятся в памяти.
+#[derive(Clone, Default, Debug)]
+pub struct LinkRecord {
+    /// Числовой идентификатор связи
+    pub id: usize,
+
+    /// Идентификатор лексемы с исходной стороны связи
+    pub from: usize,
+
+    /// Идентификатор лексемы с конечной стороны связи
+    pub to: usize,
+
+    /// Тип связи
+    pub kind: Arc<LinkKind>,
+}
+
+fn string_from_bytes(b: &[u8]) -> std::result::Result<String, std::str::Utf8Error> {
+    let s = std::str::from_utf8(b)?;
+    Ok(s.to_owned())
+}
+
+fn unescaped_string_from_bytes(b: &[u8]) -> Result<String> {
+    let unescaped = quick_xml::escape::unescape(b).map_err(quick_xml::Error::from)?;
+    Ok(string_from_bytes(&unescaped)?)
+}
+
+fn integer_from_bytes<N>(b: &[u8]) -> Result<N>
+where
+    N: std::str::FromStr<Err = std::num::ParseIntError>,
+{
+    let s = std::str::from_utf8(b)?;
+    Ok(s.parse()?)
+}
+
+fn get_restriction_scope(el: &BytesStart) -> Result<RestrictionScope> {
+    for attr in el.attributes() {
+        let Attribute { key: name, value } = attr?;
+        if name == b"type" {
+            return match &*value {
+                b"lemma" => Ok(RestrictionScope::Lemma),
+                b"form" => Ok(RestrictionScope::Form),
+                _ => {
+                    let s = std::str::from_utf8(&value)?;
+                    let errmsg = format!("invalid restriction scope: '{}'", s);
+                    Err(Error::Parsing(errmsg))
+                }
+            };
+        }
+    }
+    Err(Error::Parsing("restriction scope isn't found".to_owned()))
+}
+
+fn get_grammeme(map: &HashMap<String, Arc<Grammeme>>, name: &[u8]) -> Result<Arc<Grammeme>> {
+    let s = string_from_bytes(name)?;
+    match map.get(&s) {
+        Some(grm) => Ok(grm.clone()),
+        None => Err(Error::UnknownGrammeme(s)),
+    }
+}
+
+/// Потоковый читатель XML-словаря.
+///
+/// В отличие от [`Dict::read_from_xml`](struct.Dict.html#method.read_from_xml)
+/// не накапливает лексемы и связи в памяти, а отдаёт их по одной.
+/// Разделы словаря читаются в порядке их следования в файле: заголовок, граммемы,
+/// ограничения, лексемы, типы связей и связи. Граммемы, ограничения и типы связей
+/// невелики и сохраняются внутри читателя. Если запросить более поздний раздел,
+/// не дочитав предыдущие, непрочитанные лексемы и связи будут пропущены.
+/// Лексемы при этом пропускаются без разбора, и ошибки в них не обнаруживаются;
+/// исключение — чтение с условием отбора
+/// ([`ParseOptions::lemma_filter`](struct.ParseOptions.html#structfield.lemma_filter)),
+/// при котором заголовки лексем разбираются, чтобы пропустить связи с отклонёнными лексемами.
+/// Разделы, отключённые в [`ParseOptions`](struct.ParseOptions.html), пропускаются
+/// без разбора: соответствующие методы возвращают пустые списки. Исключение —
+/// разделы, без которых нельзя разобрать включённые: граммемы читаются и
+/// возвращаются, если читаются ограничения или лексемы, а типы связей — если
+/// читаются связи.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// use opencorpora::DictReader;
+///
+/// let file = File::open("dict.xml").unwrap();
+/// let mut reader = DictReader::new(BufReader::new(file)).unwrap();
+/// println!("Grammemes count: {}", reader.grammemes().unwrap().len());
+/// let mut lemmata_count = 0;
+/// for lemma in reader.lemmata() {
+///     let _lemma = lemma.unwrap();
+///     lemmata_count += 1;
+/// }
+/// println!("Lemmata count: {}", lemmata_count);
+/// ```
+pub struct DictReader<R: BufRead> {
+    reader: Reader<LineCounter<R>>,
+    buf: Vec<u8>,
+    state: ParsingState,
+    closed: Section,
+    position: Position,
+    lemma_id: Option<usize>,
+    options: ParseOptions,
+    diagnostics: Vec<ParseError>,
+
+    version: String,
+    revision: usize,
+    grammemes: Vec<Arc<Grammeme>>,
+    restrictions: Vec<Restriction>,
+    link_kinds: Vec<Arc<LinkKind>>,
+
+    grammeme_by_name: HashMap<String, Arc<Grammeme>>,
+    rejected_lemmata: HashSet<usize>,
+
+    current_grammeme: Grammeme,
+    current_restriction: Restriction,
+    current_lemma: Lemma,
+    current_form: Form,
+    current_link_kind: LinkKind,
+}
+
+impl<R: BufRead> DictReader<R> {
+    /// создаёт читателя и читает заголовок словаря.
+    pub fn new(r: R) -> Result<Self> {
+        DictReader::with_options(r, ParseOptions::default())
+    }
+
+    /// создаёт читателя с параметрами `options` и читает заголовок словаря.
+    pub fn with_options(r: R, options: ParseOptions) -> Result<Self> {
+        let mut reader = DictReader {
+            reader: Reader::from_reader(LineCounter::new(r)),
+            buf: Vec::new(),
+            state: ParsingState::Start,
+            closed: Section::Header,
+            position: Position::default(),
+            lemma_id: None,
+            options,
+            diagnostics: Vec::new(),
+            version: String::new(),
+            revision: 0,
+            grammemes: Vec::new(),
+            restrictions: Vec::new(),
+            link_kinds: Vec::new(),
+            grammeme_by_name: HashMap::new(),
+            rejected_lemmata: HashSet::new(),
+            current_grammeme: Grammeme::default(),
+            current_restriction: Restriction::default(),
+            current_lemma: Lemma::default(),
+            current_form: Form::default(),
+            current_link_kind: LinkKind::default(),
+        };
+        while reader.state == ParsingState::Start {
+            reader.step()?;
+        }
+        Ok(reader)
+    }
+
+    /// возвращает версию словаря.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// возвращает номер ревизии словаря.
+    pub fn revision(&self) -> usize {
+        self.revision
+    }
+
+    /// дочитывает раздел граммем и возвращает все граммемы.
+    pub fn grammemes(&mut self) -> Result<&[Arc<Grammeme>]> {
+        self.read_through(Section::Grammemes)?;
+        Ok(&self.grammemes)
+    }
+
+    /// дочитывает раздел ограничений и возвращает все ограничения.
+    pub fn restrictions(&mut self) -> Result<&[Restriction]> {
+        self.read_through(Section::Restrictions)?;
+        Ok(&self.restrictions)
+    }
+
+    /// возвращает итератор по лексемам словаря.
+    pub fn lemmata(&mut self) -> Lemmata<'_, R> {
+        Lemmata { reader: self, done: false }
+    }
+
+    /// дочитывает раздел типов связей и возвращает все типы связей.
+    pub fn link_kinds(&mut self) -> Result<&[Arc<LinkKind>]> {
+        self.read_through(Section::LinkTypes)?;
+        Ok(&self.link_kinds)
+    }
+
+    /// возвращает итератор по связям между лексемами.
+    pub fn links(&mut self) -> Links<'_, R> {
+        Links { reader: self, done: false }
+    }
+
+    /// возвращает ошибки, пропущенные в нестрогом режиме
+    /// (см. [`ParseOptions::strict`](struct.ParseOptions.html#structfield.strict)),
+    /// и очищает их список.
+    pub fn take_diagnostics(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// дочитывает словарь до конца и проверяет, что он завершён корректно.
+    pub fn finish(mut self) -> Result<()> {
+        self.read_through(Section::End)
+    }
+
+    fn is_past(&self, section: Section) -> bool {
+        match self.state {
+            ParsingState::Dictionary => self.closed >= section,
+            ParsingState::End => true,
+            ref state => state.section() > section,
+        }
+    }
+
+    fn read_through(&mut self, section: Section) -> Result<()> {
+        while !self.is_past(section) {
+            // без условия отбора непрочитанные лексемы не нужны даже для пропуска связей
+            if self.state == ParsingState::Lemmata
+                && section > Section::Lemmata
+                && self.options.lemma_filter.is_none()
+            {
+                self.state = ParsingState::Dictionary;
+                self.skip_section(b"lemmata", Section::Lemmata).map_err(|e| self.locate(e))?;
+                continue;
+            }
+            if let Step::Eof = self.step()? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// дополняет ошибку позицией последнего прочитанного XML-события,
+    /// состоянием читателя и идентификатором лексемы, внутри которой оно находится.
+    ///
+    /// Ошибки, возвращаемые читателем, уже содержат эти сведения; метод
+    /// нужен для ошибок, обнаруженных при обработке прочитанных лексем и связей.
+    pub fn locate(&self, error: Error) -> Error {
+        match error {
+            Error::Located(_) => error,
+            error => Error::Located(Box::new(self.context(error))),
+        }
+    }
+
+    fn context(&self, error: Error) -> ParseError {
+        ParseError {
+            offset: self.position.offset,
+            line: self.position.line,
+            column: self.position.column,
+            state: self.state,
+            lemma_id: self.lemma_id,
+            source: error,
+        }
+    }
+
+    fn step(&mut self) -> Result<Step> {
+        let mut buf = std::mem::take(&mut self.buf);
+        buf.clear();
+        let counter = self.reader.get_ref();
+        let offset = self.reader.buffer_position();
+        self.position = Position {
+            offset,
+            line: counter.line,
+            // quick-xml может уже прочитать символ '<' следующего тега
+            column: counter.column.saturating_sub(counter.consumed - offset).max(1),
+        };
+        if !matches!(self.state, ParsingState::Lemma | ParsingState::LemmaL | ParsingState::LemmaF) {
+            self.lemma_id = None;
+        }
+        let step = match self.reader.read_event(&mut buf) {
+            Ok(event) => self.handle(event),
+            Err(e) => Err(e.into()),
+        };
+        self.buf = buf;
+        match step {
+            Err(e) if !self.options.strict && self.is_recoverable(&e) => {
+                let diagnostic = self.context(e);
+                self.recover()?;
+                self.diagnostics.push(diagnostic);
+                Ok(Step::Continue)
+            }
+            step => step.map_err(|e| self.locate(e)),
+        }
+    }
+
+    /// проверяет, можно ли пропустить элемент, при обработке которого произошла ошибка.
+    fn is_recoverable(&self, error: &Error) -> bool {
+        let recoverable_state = matches!(
+            self.state,
+            ParsingState::Restriction
+                | ParsingState::RestrictionLeft
+                | ParsingState::RestrictionRight
+                | ParsingState::Lemma
+                | ParsingState::LemmaL
+                | ParsingState::LemmaF
+                | ParsingState::Links
+        );
+        // после ошибки в разметке или конца файла продолжить чтение нельзя
+        recoverable_state && !matches!(error, Error::Xml { .. } | Error::Io { .. } | Error::UnexpectedEof)
+    }
+
+    /// проверяет, нужно ли читать раздел `section` при заданных параметрах.
+    fn is_loaded(&self, section: Section) -> bool {
+        let options = &self.options;
+        match section {
+            // без граммем нельзя разобрать ограничения и лексемы
+            Section::Grammemes => options.grammemes || options.restrictions || options.lemmata,
+            Section::Restrictions => options.restrictions,
+            Section::Lemmata => options.lemmata,
+            // без типов связей нельзя разобрать связи
+            Section::LinkTypes => options.link_types || options.links,
+            Section::Links => options.links,
+            Section::Header | Section::End => true,
+        }
+    }
+
+    /// пропускает раздел целиком, не разбирая его элементы.
+    fn skip_section(&mut self, name: &[u8], section: Section) -> Result<()> {
+        let mut buf = Vec::new();
+        self.reader.read_to_end(name, &mut buf)?;
+        self.closed = section;
+        Ok(())
+    }
+
+    /// пропускает формы лексемы, отклонённой условием отбора.
+    fn reject_lemma(&mut self) -> Result<()> {
+        let mut buf = Vec::new();
+        self.reader.read_to_end(b"lemma", &mut buf)?;
+        self.state = ParsingState::Lemmata;
+        self.rejected_lemmata.insert(self.current_lemma.id);
+        self.current_lemma = Lemma::default();
+        Ok(())
+    }
+
+    /// пропускает оставшуюся часть элемента, при обработке которого произошла ошибка.
+    fn recover(&mut self) -> Result<()> {
+        let mut buf = std::mem::take(&mut self.buf);
+        let result = match self.state {
+            ParsingState::Restriction | ParsingState::RestrictionLeft | ParsingState::RestrictionRight => {
+                self.state = ParsingState::Restrictions;
+                self.reader.read_to_end(b"restr", &mut buf)
+            }
+            ParsingState::Lemma | ParsingState::LemmaL | ParsingState::LemmaF => {
+                self.state = ParsingState::Lemmata;
+                self.current_lemma = Lemma::default();
+                self.reader.read_to_end(b"lemma", &mut buf)
+            }
+            // связь записывается пустым элементом, пропускать нечего
+            _ => Ok(()),
+        };
+        self.buf = buf;
+        result.map_err(|e| self.locate(e.into()))
+    }
+
+    fn handle(&mut self, event: Event) -> Result<Step> {
+        match event {
+            Event::Empty(ref el) => self.handle_empty(el),
+            Event::Start(ref el) => self.handle_start(el).map(|_| Step::Continue),
+            Event::Text(ref el) => {
+                match self.state {
+                    ParsingState::GrammemeName => {
+                        self.current_grammeme.name = string_from_bytes(&el.unescaped()?)?;
+                    }
+                    ParsingState::GrammemeAlias => {
+                        self.current_grammeme.alias = string_from_bytes(&el.unescaped()?)?;
+                    }
+                    ParsingState::GrammemeDescription => {
+                        self.current_grammeme.description = string_from_bytes(&el.unescaped()?)?;
+                    }
+                    ParsingState::RestrictionLeft => {
+                        if !el.is_empty() {
+                            self.current_restriction.left_grammeme =
+                                Some(get_grammeme(&self.grammeme_by_name, &el.unescaped()?)?);
+                        } else {
+                            self.current_restriction.left_grammeme = None;
+                        }
+                    }
+                    ParsingState::RestrictionRight => {
+                        if !el.is_empty() {
+                            self.current_restriction.right_grammeme =
+                                Some(get_grammeme(&self.grammeme_by_name, &el.unescaped()?)?);
+                        } else {
+                            self.current_restriction.right_grammeme = None;
+                        }
+                    }
+                    ParsingState::LinkType => {
+                        self.current_link_kind.name = string_from_bytes(&el.unescaped()?)?;
+                    }
+                    _ => (),
+                }
+                Ok(Step::Continue)
+            }
+            Event::End(ref el) => self.handle_end(el.name()),
+            Event::Eof => {
+                if self.state != ParsingState::End {
+                    Err(Error::UnexpectedEof)
+                } else {
+                    Ok(Step::Eof)
+                }
+            }
+            Event::Decl(_) | Event::Comment(_) | Event::PI(_) | Event::DocType(_) => Ok(Step::Continue),
+            Event::CData(_) => Err(Error::UnexpectedEvent("CDATA section".to_owned())),
+        }
+    }
+
+    fn handle_empty(&mut self, el: &BytesStart) -> Result<Step> {
+        match el.name() {
+            b"g" if self.state == ParsingState::LemmaL => {
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    if name == b"v" {
+                        let grammeme = get_grammeme(&self.grammeme_by_name, &value)?;
+                        self.current_lemma.grammemes.push(grammeme);
+                    }
+                }
+                Ok(Step::Continue)
+            }
+            b"g" if self.state == ParsingState::LemmaF => {
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    if name == b"v" {
+                        let grammeme = get_grammeme(&self.grammeme_by_name, &value)?;
+                        self.current_form.grammemes.push(grammeme);
+                    }
+                }
+                Ok(Step::Continue)
+            }
+            b"link" if self.state == ParsingState::Links => {
+                let mut current_link = LinkRecord::default();
+                let mut kind_id = 0;
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    match name {
+                        b"id" => {
+                            current_link.id = integer_from_bytes(&value)?;
+                        }
+                        b"from" => {
+                            current_link.from = integer_from_bytes(&value)?;
+                        }
+                        b"to" => {
+                            current_link.to = integer_from_bytes(&value)?;
+                        }
+                        b"type" => {
+                            kind_id = integer_from_bytes(&value)?;
+                        }
+                        _ => (),
+                    }
+                }
+                if self.rejected_lemmata.contains(&current_link.from)
+                    || self.rejected_lemmata.contains(&current_link.to)
+                {
+                    return Ok(Step::Continue);
+                }
+                current_link.kind = match self.link_kinds.iter().find(|lk| lk.id == kind_id) {
+                    Some(lk) => lk.clone(),
+                    None => return Err(Error::UnknownLinkKind(kind_id)),
+                };
+                Ok(Step::Link(current_link))
+            }
+            name => {
+                let s = std::str::from_utf8(name)?;
+                Err(Error::Parsing(format!("unexpected single tag: '{}'", s)))
+            }
+        }
+    }
+
+    fn handle_start(&mut self, el: &BytesStart) -> Result<()> {
+        match el.name() {
+            b"dictionary" if self.state == ParsingState::Start => {
+                self.state = ParsingState::Dictionary;
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    match name {
+                        b"version" => {
+                            self.version = unescaped_string_from_bytes(&value)?;
+                        }
+                        b"revision" => {
+                            self.revision = integer_from_bytes(&value)?;
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            b"grammemes" if self.state == ParsingState::Dictionary => {
+                if !self.is_loaded(Section::Grammemes) {
+                    return self.skip_section(b"grammemes", Section::Grammemes);
+                }
+                self.state = ParsingState::Grammemes;
+                self.grammemes.clear();
+                self.grammeme_by_name.clear();
+            }
+            b"grammeme" if self.state == ParsingState::Grammemes => {
+                self.state = ParsingState::Grammeme;
+                self.current_grammeme = Grammeme::default();
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    if name == b"parent" {
+                        if value.is_empty() {
+                            self.current_grammeme.parent = None;
+                        } else {
+                            self.current_grammeme.parent = Some(unescaped_string_from_bytes(&value)?);
+                        }
+                    }
+                }
+            }
+            b"name" if self.state == ParsingState::Grammeme => {
+                self.state = ParsingState::GrammemeName;
+            }
+            b"alias" if self.state == ParsingState::Grammeme => {
+                self.state = ParsingState::GrammemeAlias;
+            }
+            b"description" if self.state == ParsingState::Grammeme => {
+                self.state = ParsingState::GrammemeDescription;
+            }
+            b"restrictions" if self.state == ParsingState::Dictionary => {
+                if !self.is_loaded(Section::Restrictions) {
+                    return self.skip_section(b"restrictions", Section::Restrictions);
+                }
+                self.state = ParsingState::Restrictions;
+                self.restrictions.clear();
+            }
+            b"restr" if self.state == ParsingState::Restrictions => {
+                self.state = ParsingState::Restriction;
+                self.current_restriction = Restriction::default();
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    match name {
+                        b"type" => match &*value {
+                            b"maybe" => {
+                                self.current_restriction.kind = RestrictionKind::Maybe;
+                            }
+                            b"obligatory" => {
+                                self.current_restriction.kind = RestrictionKind::Obligatory;
+                            }
+                            b"forbidden" => {
+                                self.current_restriction.kind = RestrictionKind::Forbidden;
+                            }
+                            _ => {
+                                let s = std::str::from_utf8(&value)?;
+                                let errmsg = format!("invalid restriction kind: '{}'", s);
+                                return Err(Error::Parsing(errmsg));
+                            }
+                        },
+                        b"auto" => {
+                            self.current_restriction.auto = integer_from_bytes(&value)?;
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            b"left" if self.state == ParsingState::Restriction => {
+                self.state = ParsingState::RestrictionLeft;
+                self.current_restriction.left_scope = get_restriction_scope(el)?;
+            }
+            b"right" if self.state == ParsingState::Restriction => {
+                self.state = ParsingState::RestrictionRight;
+                self.current_restriction.right_scope = get_restriction_scope(el)?;
+            }
+            b"lemmata" if self.state == ParsingState::Dictionary => {
+                if !self.is_loaded(Section::Lemmata) {
+                    return self.skip_section(b"lemmata", Section::Lemmata);
+                }
+                self.state = ParsingState::Lemmata;
+            }
+            b"lemma" if self.state == ParsingState::Lemmata => {
+                self.state = ParsingState::Lemma;
+                self.current_lemma = Lemma::default();
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    match name {
+                        b"id" => {
+                            self.current_lemma.id = integer_from_bytes(&value)?;
+                            self.lemma_id = Some(self.current_lemma.id);
+                        }
+                        b"rev" => {
+                            self.current_lemma.revision = integer_from_bytes(&value)?;
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            b"l" if self.state == ParsingState::Lemma => {
+                self.state = ParsingState::LemmaL;
+                self.current_lemma.grammemes.clear();
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    if name == b"t" {
+                        self.current_lemma.word = unescaped_string_from_bytes(&value)?;
+                    }
+                }
+            }
+            b"f" if self.state == ParsingState::Lemma => {
+                self.state = ParsingState::LemmaF;
+                self.current_form = Form::default();
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    if name == b"t" {
+                        self.current_form.word = unescaped_string_from_bytes(&value)?;
+                    }
+                }
+            }
+            b"link_types" if self.state == ParsingState::Dictionary => {
+                if !self.is_loaded(Section::LinkTypes) {
+                    return self.skip_section(b"link_types", Section::LinkTypes);
+                }
+                self.state = ParsingState::LinkTypes;
+                self.link_kinds.clear();
+            }
+            b"type" if self.state == ParsingState::LinkTypes => {
+                self.state = ParsingState::LinkType;
+                self.current_link_kind = LinkKind::default();
+                for attr in el.attributes() {
+                    let Attribute { key: name, value } = attr?;
+                    if name == b"id" {
+                        self.current_link_kind.id = integer_from_bytes(&value)?;
+                    }
+                }
+            }
+            b"links" if self.state == ParsingState::Dictionary => {
+                if !self.is_loaded(Section::Links) {
+                    return self.skip_section(b"links", Section::Links);
+                }
+                self.state = ParsingState::Links;
+            }
+            name => {
+                let s = std::str::from_utf8(name)?;
+                return Err(Error::Parsing(format!("unexpected opening tag: '{}'", s)));
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_end(&mut self, name: &[u8]) -> Result<Step> {
+        match name {
+            b"dictionary" if self.state == ParsingState::Dictionary => {
+                self.state = ParsingState::End;
+            }
+            b"grammemes" if self.state == ParsingState::Grammemes => {
+                GrammemeTree::new(&self.grammemes)?;
+                self.state = ParsingState::Dictionary;
+                self.closed = Section::Grammemes;
+            }
+            b"grammeme" if self.state == ParsingState::Grammeme => {
+                self.state = ParsingState::Grammemes;
+                if self.grammemes.len() >= GrammemeSet::CAPACITY {
+                    return Err(Error::TooManyGrammemes);
+                }
+                self.current_grammeme.index = self.grammemes.len();
+                let last_grammeme = Arc::new(std::mem::take(&mut self.current_grammeme));
+                self.grammemes.push(last_grammeme.clone());
+                self.grammeme_by_name.insert(last_grammeme.name.clone(), last_grammeme);
+            }
+            b"name" if self.state == ParsingState::GrammemeName => {
+                self.state = ParsingState::Grammeme;
+            }
+            b"alias" if self.state == ParsingState::GrammemeAlias => {
+                self.state = ParsingState::Grammeme;
+            }
+            b"description" if self.state == ParsingState::GrammemeDescription => {
+                self.state = ParsingState::Grammeme;
+            }
+            b"restrictions" if self.state == ParsingState::Restrictions => {
+                self.state = ParsingState::Dictionary;
+                self.closed = Section::Restrictions;
+            }
+            b"restr" if self.state == ParsingState::Restriction => {
+                self.state = ParsingState::Restrictions;
+                self.restrictions.push(self.current_restriction.clone());
+            }
+            b"left" if self.state == ParsingState::RestrictionLeft => {
+                self.state = ParsingState::Restriction;
+            }
+            b"right" if self.state == ParsingState::RestrictionRight => {
+                self.state = ParsingState::Restriction;
+            }
+            b"lemmata" if self.state == ParsingState::Lemmata => {
+                self.state = ParsingState::Dictionary;
+                self.closed = Section::Lemmata;
+            }
+            b"lemma" if self.state == ParsingState::Lemma => {
+                self.state = ParsingState::Lemmata;
+                return Ok(Step::Lemma(std::mem::take(&mut self.current_lemma)));
+            }
+            b"l" if self.state == ParsingState::LemmaL => {
+                self.state = ParsingState::Lemma;
+                let rejected = match self.options.lemma_filter {
+                    Some(ref filter) => !filter(&self.current_lemma),
+                    None => false,
+                };
+                if rejected {
+                    self.reject_lemma()?;
+                }
+            }
+            b"f" if self.state == ParsingState::LemmaF => {
+                self.state = ParsingState::Lemma;
+                self.current_lemma.forms.push(std::mem::take(&mut self.current_form));
+            }
+            b"link_types" if self.state == ParsingState::LinkTypes => {
+                self.state = ParsingState::Dictionary;
+                self.closed = Section::LinkTypes;
+            }
+            b"type" if self.state == ParsingState::LinkType => {
+                self.state = ParsingState::LinkTypes;
+                let last_link_kind = std::mem::take(&mut self.current_link_kind);
+                self.link_kinds.push(Arc::new(last_link_kind));
+            }
+            b"links" if self.state == ParsingState::Links => {
+                self.state = ParsingState::Dictionary;
+                self.closed = Section::Links;
+            }
+            name => {
+                let s = std::str::from_utf8(name)?;
+                return Err(Error::Parsing(format!("unexpected closing tag: '{}'", s)));
+            }
+        }
+        Ok(Step::Continue)
+    }
+}
+
+/// Открывающий тег, который читается перед частью раздела лексем
+/// (см. [`DictReader::lemmata_chunk`]).
+#[cfg(feature = "parallel")]
+pub(crate) const CHUNK_PREFIX: &[u8] = b"<lemmata>";
+
+/// Закрывающий тег, который читается после части раздела лексем.
+#[cfg(feature = "parallel")]
+const CHUNK_SUFFIX: &[u8] = b"</lemmata>";
+
+/// Открывающие теги, которые читаются перед оставшейся частью словаря
+/// (см. [`DictReader::lemmata_tail`]).
+#[cfg(feature = "parallel")]
+pub(crate) const TAIL_PREFIX: &[u8] = b"<dictionary><lemmata>";
+
+/// Чтение раздела лексем по частям для параллельного чтения словаря.
+///
+/// Каждая часть читается отдельным читателем, перед данными которого стоят
+/// копии открывающих тегов из других частей файла. Так парность тегов
+/// проверяется, как при последовательном чтении, а позиции в сообщениях
+/// об ошибках отсчитываются от начала добавленных тегов.
+#[cfg(feature = "parallel")]
+impl<R: BufRead> DictReader<R> {
+    /// дочитывает словарь до начала раздела лексем и возвращает
+    /// смещение первого байта после открывающего тега `lemmata`
+    /// или `None`, если раздела лексем в словаре нет.
+    pub(crate) fn enter_lemmata(&mut self) -> Result<Option<usize>> {
+        while self.state != ParsingState::Lemmata {
+            if self.is_past(Section::Lemmata) {
+                return Ok(None);
+            }
+            self.step()?;
+        }
+        Ok(Some(self.reader.buffer_position()))
+    }
+
+    /// создаёт читателя части раздела лексем `chunk`, состоящей из целых
+    /// элементов `lemma`.
+    ///
+    /// Часть читается между тегами [`CHUNK_PREFIX`] и `</lemmata>`, поэтому
+    /// незакрытый в ней элемент обнаруживается в её конце.
+    pub(crate) fn lemmata_chunk<'b>(&self, chunk: &'b [u8]) -> Result<DictReader<impl BufRead + 'b>> {
+        self.resume(CHUNK_PREFIX, CHUNK_PREFIX.chain(chunk).chain(CHUNK_SUFFIX))
+    }
+
+    /// создаёт читателя оставшейся части словаря `tail`, начинающейся
+    /// с закрывающего тега `lemmata`. Перед `tail` читаются теги [`TAIL_PREFIX`].
+    pub(crate) fn lemmata_tail<'b>(&self, tail: &'b [u8]) -> Result<DictReader<impl BufRead + 'b>> {
+        self.resume(TAIL_PREFIX, TAIL_PREFIX.chain(tail))
+    }
+
+    /// возвращает идентификаторы лексем, отклонённых условием отбора.
+    pub(crate) fn take_rejected_lemmata(&mut self) -> HashSet<usize> {
+        std::mem::take(&mut self.rejected_lemmata)
+    }
+
+    /// добавляет идентификаторы лексем, связи с которыми нужно пропустить.
+    pub(crate) fn reject_lemmata<I: IntoIterator<Item = usize>>(&mut self, ids: I) {
+        self.rejected_lemmata.extend(ids);
+    }
+
+    /// создаёт читателя, продолжающего чтение внутри раздела лексем
+    /// с уже прочитанными заголовком, граммемами и ограничениями.
+    /// Открывающие теги `prefix`, с которых начинаются данные `r`, пропускаются.
+    fn resume<S: BufRead>(&self, prefix: &[u8], r: S) -> Result<DictReader<S>> {
+        let mut reader = DictReader {
+            reader: Reader::from_reader(LineCounter::new(r)),
+            buf: Vec::new(),
+            state: ParsingState::Lemmata,
+            closed: Section::Restrictions,
+            position: Position::default(),
+            lemma_id: None,
+            options: self.options.clone(),
+            diagnostics: Vec::new(),
+            version: self.version.clone(),
+            revision: self.revision,
+            grammemes: self.grammemes.clone(),
+            restrictions: self.restrictions.clone(),
+            link_kinds: Vec::new(),
+            grammeme_by_name: self.grammeme_by_name.clone(),
+            rejected_lemmata: HashSet::new(),
+            current_grammeme: Grammeme::default(),
+            current_restriction: Restriction::default(),
+            current_lemma: Lemma::default(),
+            current_form: Form::default(),
+            current_link_kind: LinkKind::default(),
+        };
+        let mut buf = Vec::new();
+        while reader.reader.buffer_position() < prefix.len() {
+            reader.reader.read_event(&mut buf)?;
+        }
+        Ok(reader)
+    }
+}
+
+/// Итератор по лексемам словаря.
+///
+/// Создаётся методом [`DictReader::lemmata`](struct.DictReader.html#method.lemmata).
+pub struct Lemmata<'a, R: BufRead> {
+    reader: &'a mut DictReader<R>,
+    done: bool,
+}
+
+impl<'a, R: BufRead> Lemmata<'a, R> {
+    /// дополняет ошибку позицией последней прочитанной лексемы
+    /// (см. [`DictReader::locate`](struct.DictReader.html#method.locate)).
+    pub fn locate(&self, error: Error) -> Error {
+        self.reader.locate(error)
+    }
+}
+
+impl<'a, R: BufRead> Iterator for Lemmata<'a, R> {
+    type Item = Result<Lemma>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done && !self.reader.is_past(Section::Lemmata) {
+            match self.reader.step() {
+                Ok(Step::Lemma(lemma)) => return Some(Ok(lemma)),
+                Ok(Step::Eof) => break,
+                Ok(_) => (),
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+/// Итератор по связям между лексемами.
+///
+/// Создаётся методом [`DictReader::links`](struct.DictReader.html#method.links).
+pub struct Links<'a, R: BufRead> {
+    reader: &'a mut DictReader<R>,
+    done: bool,
+}
+
+impl<'a, R: BufRead> Links<'a, R> {
+    /// дополняет ошибку позицией последней прочитанной связи
+    /// (см. [`DictReader::locate`](struct.DictReader.html#method.locate)).
+    pub fn locate(&self, error: Error) -> Error {
+        self.reader.locate(error)
+    }
+}
+
+impl<'a, R: BufRead> Iterator for Links<'a, R> {
+    type Item = Result<LinkRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // непрочитанные лексемы пропускаются так же, как при чтении типов связей
+        if let Err(e) = self.reader.read_through(Section::LinkTypes) {
+            self.done = true;
+            return Some(Err(e));
+        }
+        while !self.done && !self.reader.is_past(Section::Links) {
+            match self.reader.step() {
+                Ok(Step::Link(link)) => return Some(Ok(link)),
+                Ok(Step::Eof) => break,
+                Ok(_) => (),
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        self.done = true;
+        None
+    }
+}
@@ -0,0 +1,175 @@
+//! Сериализация ссылок между элементами словаря.
+//!
+//! Граммемы записываются именами, лексемы и типы связей — идентификаторами.
+//! При чтении вместо ссылок создаются заглушки, которые при десериализации
+//! [`Dict`](../struct.Dict.html) заменяются элементами словаря, а в отдельно
+//! прочитанных лексемах — методом [`Dict::resolve`](../struct.Dict.html#method.resolve).
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::dict::{Dict, Form, Grammeme, Lemma, Link, LinkKind, Restriction};
+use crate::error::Error;
+use crate::grammeme_set::GrammemeSet;
+
+fn grammeme_stub(name: String) -> Arc<Grammeme> {
+    // номер заглушки вне множества граммем: до замены она не совпадает ни с одной граммемой
+    Arc::new(Grammeme {
+        name,
+        ..Grammeme::default()
+    })
+}
+
+pub(crate) mod grammemes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(grammemes: &[Arc<Grammeme>], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(grammemes.iter().map(|g| &g.name))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Arc<Grammeme>>, D::Error> {
+        let names = Vec::<String>::deserialize(d)?;
+        Ok(names.into_iter().map(grammeme_stub).collect())
+    }
+}
+
+pub(crate) mod optional_grammeme {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(grammeme: &Option<Arc<Grammeme>>, s: S) -> Result<S::Ok, S::Error> {
+        match *grammeme {
+            Some(ref grammeme) => s.serialize_some(&grammeme.name),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Arc<Grammeme>>, D::Error> {
+        Ok(Option::<String>::deserialize(d)?.map(grammeme_stub))
+    }
+}
+
+pub(crate) mod lemma {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(lemma: &Arc<Lemma>, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(lemma.id as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Arc<Lemma>, D::Error> {
+        Ok(Arc::new(Lemma {
+            id: usize::deserialize(d)?,
+            ..Lemma::default()
+        }))
+    }
+}
+
+pub(crate) mod link_kind {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(kind: &Arc<LinkKind>, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(kind.id as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Arc<LinkKind>, D::Error> {
+        Ok(Arc::new(LinkKind {
+            id: usize::deserialize(d)?,
+            ..LinkKind::default()
+        }))
+    }
+}
+
+/// Словарь в том виде, в котором он десериализуется до разрешения ссылок.
+#[derive(Deserialize)]
+pub(crate) struct DictData {
+    version: String,
+    revision: usize,
+    grammemes: Vec<Grammeme>,
+    restrictions: Vec<Restriction>,
+    lemmata: Vec<Lemma>,
+    link_kinds: Vec<LinkKind>,
+    links: Vec<Link>,
+}
+
+impl TryFrom<DictData> for Dict {
+    type Error = String;
+
+    fn try_from(data: DictData) -> Result<Dict, String> {
+        if data.grammemes.len() > GrammemeSet::CAPACITY {
+            return Err(Error::TooManyGrammemes.to_string());
+        }
+        let grammemes: Vec<Arc<Grammeme>> = data
+            .grammemes
+            .into_iter()
+            .enumerate()
+            .map(|(index, grammeme)| Arc::new(Grammeme { index, ..grammeme }))
+            .collect();
+        let grammeme_by_name: HashMap<&str, &Arc<Grammeme>> =
+            grammemes.iter().map(|g| (g.name.as_str(), g)).collect();
+        let resolve = |stub: &Arc<Grammeme>| match grammeme_by_name.get(stub.name.as_str()) {
+            Some(&grammeme) => Ok(grammeme.clone()),
+            None => Err(format!("invalid grammeme name: '{}'", stub.name)),
+        };
+        let resolve_all =
+            |stubs: &[Arc<Grammeme>]| stubs.iter().map(resolve).collect::<Result<Vec<_>, String>>();
+
+        let mut restrictions = Vec::with_capacity(data.restrictions.len());
+        for restriction in data.restrictions {
+            restrictions.push(Restriction {
+                left_grammeme: restriction.left_grammeme.as_ref().map(resolve).transpose()?,
+                right_grammeme: restriction.right_grammeme.as_ref().map(resolve).transpose()?,
+                ..restriction
+            });
+        }
+
+        let mut lemmata = Vec::with_capacity(data.lemmata.len());
+        for lemma in data.lemmata {
+            let mut forms = Vec::with_capacity(lemma.forms.len());
+            for form in &lemma.forms {
+                forms.push(Form {
+                    word: form.word.clone(),
+                    grammemes: resolve_all(&form.grammemes)?,
+                });
+            }
+            lemmata.push(Arc::new(Lemma {
+                grammemes: resolve_all(&lemma.grammemes)?,
+                forms,
+                ..lemma
+            }));
+        }
+
+        let link_kinds: Vec<Arc<LinkKind>> = data.link_kinds.into_iter().map(Arc::new).collect();
+        let mut dict = Dict {
+            version: data.version,
+            revision: data.revision,
+            grammemes,
+            restrictions,
+            lemmata,
+            link_kinds,
+            ..Dict::default()
+        };
+        dict.reindex().map_err(|e| e.to_string())?;
+
+        let get_lemma = |id: usize| match dict.lemma(id) {
+            Some(lemma) => Ok(lemma.clone()),
+            None => Err(format!("invalid lemma id: '{}'", id)),
+        };
+        let get_link_kind = |id: usize| match dict.link_kind(id) {
+            Some(kind) => Ok(kind.clone()),
+            None => Err(format!("invalid link type id: '{}'", id)),
+        };
+        let mut links = Vec::with_capacity(data.links.len());
+        for link in data.links {
+            links.push(Link {
+                id: link.id,
+                from: get_lemma(link.from.id)?,
+                to: get_lemma(link.to.id)?,
+                kind: get_link_kind(link.kind.id)?,
+            });
+        }
+        dict.links = links;
+        Ok(dict)
+    }
+}
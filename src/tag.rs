@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use crate::dict::{Dict, Form, Grammeme, Lemma};
+use crate::error::{Error, Result};
+use crate::grammeme_set::GrammemeSet;
+
+/// Способ записи граммем в текстовом представлении тега.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagStyle {
+    /// Имена граммем на латинице (`NOUN,inan,masc sing,nomn`)
+    Latin,
+
+    /// Имена граммем на кириллице (`СУЩ,неод,мр ед,им`)
+    Cyrillic,
+}
+
+/// Полный тег словоформы: граммемы лексемы и граммемы словоформы.
+///
+/// Текстовое представление тега совпадает с принятым в OpenCorpora и pymorphy2:
+/// граммемы перечисляются через запятую, граммемы лексемы отделяются от граммем
+/// словоформы пробелом, например `NOUN,anim,masc sing,nomn`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::{Dict, Tag, TagStyle};
+///
+/// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+/// let tag = Tag::parse(&dict, "NOUN,anim,masc sing,nomn").unwrap();
+/// assert_eq!(tag.to_string(), "NOUN,anim,masc sing,nomn");
+/// assert_eq!(tag.format(TagStyle::Cyrillic), "СУЩ,од,мр ед,им");
+/// ```
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct Tag {
+    /// Граммемы лексемы
+    pub lemma: Vec<Arc<Grammeme>>,
+
+    /// Граммемы словоформы
+    pub form: Vec<Arc<Grammeme>>,
+}
+
+impl Tag {
+    /// разбирает текстовое представление тега.
+    ///
+    /// Граммемы ищутся в словаре `dict` по имени на латинице, а если такого
+    /// имени нет — по имени на кириллице.
+    pub fn parse(dict: &Dict, s: &str) -> Result<Tag> {
+        let (lemma_part, form_part) = match s.find(' ') {
+            Some(pos) => (&s[..pos], Some((pos + 1, &s[pos + 1..]))),
+            None => (s, None),
+        };
+        let mut tag = Tag {
+            lemma: parse_grammemes(dict, s, 0, lemma_part)?,
+            form: Vec::new(),
+        };
+        if let Some((offset, form_part)) = form_part {
+            tag.form = parse_grammemes(dict, s, offset, form_part)?;
+        }
+        Ok(tag)
+    }
+
+    /// возвращает все граммемы тега: граммемы лексемы, за которыми следуют граммемы словоформы.
+    pub fn grammemes(&self) -> impl Iterator<Item = &Arc<Grammeme>> {
+        self.lemma.iter().chain(&self.form)
+    }
+
+    /// возвращает множество всех граммем тега.
+    pub fn grammeme_set(&self) -> GrammemeSet {
+        self.grammemes().collect()
+    }
+
+    /// возвращает текстовое представление тега.
+    pub fn format(&self, style: TagStyle) -> String {
+        let join = |grammemes: &[Arc<Grammeme>]| {
+            let names: Vec<&str> = grammemes
+                .iter()
+                .map(|g| match style {
+                    TagStyle::Latin => g.name.as_str(),
+                    TagStyle::Cyrillic => g.alias.as_str(),
+                })
+                .collect();
+            names.join(",")
+        };
+        if self.form.is_empty() {
+            join(&self.lemma)
+        } else {
+            format!("{} {}", join(&self.lemma), join(&self.form))
+        }
+    }
+}
+
+impl std::fmt::Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.format(TagStyle::Latin))
+    }
+}
+
+/// разбирает список граммем, записанных через запятую, начиная с позиции `offset` тега `tag`.
+fn parse_grammemes(dict: &Dict, tag: &str, offset: usize, part: &str) -> Result<Vec<Arc<Grammeme>>> {
+    let mut grammemes = Vec::new();
+    let mut position = offset;
+    for name in part.split(',') {
+        if !name.is_empty() {
+            let grammeme = dict.grammeme(name).or_else(|| dict.grammeme_by_alias(name));
+            match grammeme {
+                Some(grammeme) => grammemes.push(grammeme.clone()),
+                None => {
+                    return Err(Error::InvalidTag {
+                        tag: tag.to_owned(),
+                        grammeme: name.to_owned(),
+                        position,
+                    });
+                }
+            }
+        }
+        position += name.len() + 1;
+    }
+    Ok(grammemes)
+}
+
+impl Lemma {
+    /// возвращает полный тег словоформы `form` этой лексемы.
+    pub fn tag(&self, form: &Form) -> Tag {
+        Tag {
+            lemma: self.grammemes.clone(),
+            form: form.grammemes.clone(),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::dict::{Dict, Grammeme, Lemma, RestrictionKind, RestrictionScope};
+use crate::error::Result;
+use crate::grammeme_set::GrammemeSet;
+
+/// Вид нарушения правил-ограничений.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// Отсутствует граммема, требуемая обязательным правилом `Dict::restrictions[restriction]`
+    Missing { restriction: usize },
+
+    /// Граммема запрещена правилом `Dict::restrictions[restriction]`
+    Forbidden {
+        restriction: usize,
+        grammeme: Arc<Grammeme>,
+    },
+
+    /// Граммема упоминается в правилах, но ни одно из них не разрешает её в данном месте
+    NotAllowed {
+        scope: RestrictionScope,
+        grammeme: Arc<Grammeme>,
+    },
+}
+
+/// Нарушение правил-ограничений в лексеме или её словоформе.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Числовой идентификатор лексемы
+    pub lemma_id: usize,
+
+    /// Порядковый номер словоформы в `Lemma::forms`
+    /// (значение `None` индицирует нарушение на уровне лексемы)
+    pub form_index: Option<usize>,
+
+    /// Вид нарушения
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "lemma {}", self.lemma_id)?;
+        if let Some(form_index) = self.form_index {
+            write!(f, ", form {}", form_index)?;
+        }
+        match self.kind {
+            ViolationKind::Missing { restriction } => {
+                write!(f, ": obligatory restriction #{} isn't satisfied", restriction)
+            }
+            ViolationKind::Forbidden { restriction, ref grammeme } => write!(
+                f,
+                ": grammeme '{}' is forbidden by restriction #{}",
+                grammeme.name, restriction
+            ),
+            ViolationKind::NotAllowed { scope, ref grammeme } => write!(
+                f,
+                ": grammeme '{}' isn't allowed in {}",
+                grammeme.name,
+                match scope {
+                    RestrictionScope::Lemma => "lemma",
+                    RestrictionScope::Form => "form",
+                }
+            ),
+        }
+    }
+}
+
+/// Правило с граммемами, развёрнутыми в множества вместе с их потомками.
+#[derive(Debug)]
+struct Rule {
+    kind: RestrictionKind,
+    left_scope: RestrictionScope,
+    left: Option<GrammemeSet>,
+    right_scope: RestrictionScope,
+    right: Option<GrammemeSet>,
+}
+
+impl Rule {
+    fn left_matches(&self, lemma: &GrammemeSet, form: &GrammemeSet) -> bool {
+        match self.left {
+            Some(ref left) => !pick(self.left_scope, lemma, form).is_disjoint(left),
+            None => true,
+        }
+    }
+}
+
+fn pick<'s>(scope: RestrictionScope, lemma: &'s GrammemeSet, form: &'s GrammemeSet) -> &'s GrammemeSet {
+    match scope {
+        RestrictionScope::Lemma => lemma,
+        RestrictionScope::Form => form,
+    }
+}
+
+/// Проверка лексем на соответствие правилам-ограничениям `Dict::restrictions`.
+///
+/// Граммема в правиле обозначает саму граммему и всех её потомков, т.е. правило
+/// `NOUN → GNdr` выполняется, если у лексемы есть `masc`, `femn` или `neut`.
+/// Правила проверяются так:
+///
+/// * `Obligatory` — если граммема слева присутствует, граммема справа обязательна;
+/// * `Forbidden` — если граммема слева присутствует, граммема справа запрещена;
+/// * `Maybe` — если граммема слева присутствует, граммема справа допустима.
+///
+/// Если у лексемы нет словоформ, правила, требующие граммему словоформы
+/// при граммеме лексемы, нарушаются на уровне лексемы.
+///
+/// Граммемы, упомянутые справа в правилах `Maybe` или `Obligatory`, допустимы
+/// только там, где их разрешает хотя бы одно из этих правил с подходящей областью
+/// применения. Граммемы, не упомянутые в таких правилах, не ограничиваются.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opencorpora::{Dict, Validator};
+///
+/// let dict = Dict::read_from_xml(std::fs::File::open("dict.xml").unwrap()).unwrap();
+/// let validator = Validator::new(&dict).unwrap();
+/// for violation in validator.validate() {
+///     println!("{}", violation);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Validator<'a> {
+    dict: &'a Dict,
+    rules: Vec<Rule>,
+    controlled: GrammemeSet,
+}
+
+impl<'a> Validator<'a> {
+    /// создаёт проверку по правилам словаря `dict`.
+    ///
+    /// Возвращает ошибку, если иерархия граммем словаря некорректна.
+    pub fn new(dict: &'a Dict) -> Result<Validator<'a>> {
+        let tree = dict.grammeme_tree()?;
+        let expand = |grammeme: &Option<Arc<Grammeme>>| {
+            grammeme.as_ref().map(|g| {
+                let mut set: GrammemeSet = tree.descendants(&g.name).collect();
+                set.insert(g);
+                set
+            })
+        };
+
+        let rules: Vec<Rule> = dict
+            .restrictions
+            .iter()
+            .map(|r| Rule {
+                kind: r.kind,
+                left_scope: r.left_scope,
+                left: expand(&r.left_grammeme),
+                right_scope: r.right_scope,
+                right: expand(&r.right_grammeme),
+            })
+            .collect();
+
+        let controlled = rules
+            .iter()
+            .filter(|rule| rule.kind != RestrictionKind::Forbidden)
+            .filter_map(|rule| rule.right)
+            .fold(GrammemeSet::new(), |acc, right| acc | right);
+
+        Ok(Validator {
+            dict,
+            rules,
+            controlled,
+        })
+    }
+
+    /// проверяет все лексемы словаря.
+    pub fn validate(&self) -> Vec<Violation> {
+        self.dict
+            .lemmata
+            .iter()
+            .flat_map(|lemma| self.validate_lemma(lemma))
+            .collect()
+    }
+
+    /// проверяет одну лексему, например ещё не добавленную в словарь.
+    ///
+    /// Граммемы лексемы должны принадлежать словарю, по которому создана проверка.
+    pub fn validate_lemma(&self, lemma: &Lemma) -> Vec<Violation> {
+        let lemma_set = lemma.grammeme_set();
+        let form_sets: Vec<GrammemeSet> = lemma.forms.iter().map(|form| form.grammeme_set()).collect();
+        let mut violations = Vec::new();
+
+        let mut push = |form_index: Option<usize>, kind: ViolationKind| {
+            violations.push(Violation {
+                lemma_id: lemma.id,
+                form_index,
+                kind,
+            })
+        };
+
+        for (idx, rule) in self.rules.iter().enumerate() {
+            let right = match rule.right {
+                Some(ref right) if rule.kind != RestrictionKind::Maybe => right,
+                _ => continue,
+            };
+            let mut check = |form_index: Option<usize>, form_set: &GrammemeSet| {
+                if !rule.left_matches(&lemma_set, form_set) {
+                    return;
+                }
+                let present = pick(rule.right_scope, &lemma_set, form_set).intersection(right);
+                match rule.kind {
+                    RestrictionKind::Obligatory if present.is_empty() => {
+                        push(form_index, ViolationKind::Missing { restriction: idx });
+                    }
+                    RestrictionKind::Forbidden => {
+                        for grammeme in self.grammemes(&present) {
+                            push(
+                                form_index,
+                                ViolationKind::Forbidden {
+                                    restriction: idx,
+                                    grammeme: grammeme.clone(),
+                                },
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            };
+            if rule.left_scope == RestrictionScope::Lemma && rule.right_scope == RestrictionScope::Lemma {
+                check(None, &GrammemeSet::new());
+            } else if rule.left_scope == RestrictionScope::Lemma && form_sets.is_empty() {
+                // у лексемы без словоформ граммемы словоформ отсутствуют
+                check(None, &GrammemeSet::new());
+            } else {
+                for (form_index, form_set) in form_sets.iter().enumerate() {
+                    check(Some(form_index), form_set);
+                }
+            }
+        }
+
+        for grammeme in self.grammemes(&lemma_set.intersection(&self.controlled)) {
+            let allowed = self.rules.iter().any(|rule| {
+                rule.kind != RestrictionKind::Forbidden
+                    && rule.right_scope == RestrictionScope::Lemma
+                    && matches!(rule.right, Some(ref right) if right.contains(grammeme))
+                    && match rule.left_scope {
+                        RestrictionScope::Lemma => rule.left_matches(&lemma_set, &GrammemeSet::new()),
+                        RestrictionScope::Form => form_sets.iter().any(|f| rule.left_matches(&lemma_set, f)),
+                    }
+            });
+            if !allowed {
+                push(
+                    None,
+                    ViolationKind::NotAllowed {
+                        scope: RestrictionScope::Lemma,
+                        grammeme: grammeme.clone(),
+                    },
+                );
+            }
+        }
+
+        for (form_index, form_set) in form_sets.iter().enumerate() {
+            for grammeme in self.grammemes(&form_set.intersection(&self.controlled)) {
+                let allowed = self.rules.iter().any(|rule| {
+                    rule.kind != RestrictionKind::Forbidden
+                        && rule.right_scope == RestrictionScope::Form
+                        && matches!(rule.right, Some(ref right) if right.contains(grammeme))
+                        && rule.left_matches(&lemma_set, form_set)
+                });
+                if !allowed {
+                    push(
+                        Some(form_index),
+                        ViolationKind::NotAllowed {
+                            scope: RestrictionScope::Form,
+                            grammeme: grammeme.clone(),
+                        },
+                    );
+                }
+            }
+        }
+
+        violations
+    }
+
+    fn grammemes<'s>(&'s self, set: &'s GrammemeSet) -> impl Iterator<Item = &'a Arc<Grammeme>> + 's {
+        let grammemes = &self.dict.grammemes;
+        set.indices().map(move |idx| &grammemes[idx])
+    }
+}
+
+impl Dict {
+    /// проверяет все лексемы словаря на соответствие правилам-ограничениям
+    /// (см. [`Validator`](struct.Validator.html)).
+    pub fn validate(&self) -> Result<Vec<Violation>> {
+        Ok(Validator::new(self)?.validate())
+    }
+}
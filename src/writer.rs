@@ -0,0 +1,204 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+
+use crate::dict::{Dict, Grammeme, Lemma, RestrictionKind, RestrictionScope};
+use crate::error::Result;
+
+/// Писатель XML-словаря в формате dict.opcorpora.xml.
+///
+/// Каждая граммема, ограничение, лексема, тип связи и связь записываются
+/// на отдельной строке, как в файлах, распространяемых opencorpora.org.
+struct DictWriter<W: Write> {
+    writer: Writer<W>,
+}
+
+impl<W: Write> DictWriter<W> {
+    fn write_dict(&mut self, dict: &Dict) -> Result<()> {
+        self.event(Event::Decl(BytesDecl::new(b"1.0", Some(b"utf-8"), Some(b"yes"))))?;
+        self.newline()?;
+        let revision = dict.revision.to_string();
+        let el = BytesStart::borrowed_name(b"dictionary")
+            .with_attributes([("version", dict.version.as_str()), ("revision", revision.as_str())]);
+        self.event(Event::Start(el))?;
+        self.newline()?;
+
+        self.start(b"grammemes")?;
+        self.newline()?;
+        for grammeme in &dict.grammemes {
+            self.write_grammeme(grammeme)?;
+            self.newline()?;
+        }
+        self.end(b"grammemes")?;
+        self.newline()?;
+
+        self.start(b"restrictions")?;
+        self.newline()?;
+        for restriction in &dict.restrictions {
+            let kind = match restriction.kind {
+                RestrictionKind::Maybe => "maybe",
+                RestrictionKind::Obligatory => "obligatory",
+                RestrictionKind::Forbidden => "forbidden",
+            };
+            let auto = restriction.auto.to_string();
+            let el = BytesStart::borrowed_name(b"restr").with_attributes([("type", kind), ("auto", auto.as_str())]);
+            self.event(Event::Start(el))?;
+            self.write_restriction_side(b"left", restriction.left_scope, &restriction.left_grammeme)?;
+            self.write_restriction_side(b"right", restriction.right_scope, &restriction.right_grammeme)?;
+            self.end(b"restr")?;
+            self.newline()?;
+        }
+        self.end(b"restrictions")?;
+        self.newline()?;
+
+        self.start(b"lemmata")?;
+        self.newline()?;
+        for lemma in &dict.lemmata {
+            self.write_lemma(lemma)?;
+            self.newline()?;
+        }
+        self.end(b"lemmata")?;
+        self.newline()?;
+
+        self.start(b"link_types")?;
+        self.newline()?;
+        for kind in &dict.link_kinds {
+            let id = kind.id.to_string();
+            let el = BytesStart::borrowed_name(b"type").with_attributes([("id", id.as_str())]);
+            self.event(Event::Start(el))?;
+            self.text(&kind.name)?;
+            self.end(b"type")?;
+            self.newline()?;
+        }
+        self.end(b"link_types")?;
+        self.newline()?;
+
+        self.start(b"links")?;
+        self.newline()?;
+        for link in &dict.links {
+            let (id, from, to, kind) = (
+                link.id.to_string(),
+                link.from.id.to_string(),
+                link.to.id.to_string(),
+                link.kind.id.to_string(),
+            );
+            let el = BytesStart::borrowed_name(b"link").with_attributes([
+                ("id", id.as_str()),
+                ("from", from.as_str()),
+                ("to", to.as_str()),
+                ("type", kind.as_str()),
+            ]);
+            self.event(Event::Empty(el))?;
+            self.newline()?;
+        }
+        self.end(b"links")?;
+        self.newline()?;
+
+        self.end(b"dictionary")?;
+        self.newline()
+    }
+
+    fn write_grammeme(&mut self, grammeme: &Grammeme) -> Result<()> {
+        let parent = grammeme.parent.as_deref().unwrap_or("");
+        let el = BytesStart::borrowed_name(b"grammeme").with_attributes([("parent", parent)]);
+        self.event(Event::Start(el))?;
+        for (name, value) in [
+            (&b"name"[..], &grammeme.name),
+            (b"alias", &grammeme.alias),
+            (b"description", &grammeme.description),
+        ] {
+            self.start(name)?;
+            self.text(value)?;
+            self.end(name)?;
+        }
+        self.end(b"grammeme")
+    }
+
+    fn write_restriction_side(
+        &mut self,
+        name: &[u8],
+        scope: RestrictionScope,
+        grammeme: &Option<Arc<Grammeme>>,
+    ) -> Result<()> {
+        let scope = match scope {
+            RestrictionScope::Lemma => "lemma",
+            RestrictionScope::Form => "form",
+        };
+        let el = BytesStart::borrowed_name(name).with_attributes([("type", scope)]);
+        self.event(Event::Start(el))?;
+        if let Some(ref grammeme) = *grammeme {
+            self.text(&grammeme.name)?;
+        }
+        self.end(name)
+    }
+
+    fn write_lemma(&mut self, lemma: &Lemma) -> Result<()> {
+        let (id, revision) = (lemma.id.to_string(), lemma.revision.to_string());
+        let el = BytesStart::borrowed_name(b"lemma").with_attributes([("id", id.as_str()), ("rev", revision.as_str())]);
+        self.event(Event::Start(el))?;
+        self.write_word(b"l", &lemma.word, &lemma.grammemes)?;
+        for form in &lemma.forms {
+            self.write_word(b"f", &form.word, &form.grammemes)?;
+        }
+        self.end(b"lemma")
+    }
+
+    /// записывает элемент `l` или `f` с граммемами.
+    fn write_word(&mut self, name: &[u8], word: &str, grammemes: &[Arc<Grammeme>]) -> Result<()> {
+        let el = BytesStart::borrowed_name(name).with_attributes([("t", word)]);
+        self.event(Event::Start(el))?;
+        for grammeme in grammemes {
+            let el = BytesStart::borrowed_name(b"g").with_attributes([("v", grammeme.name.as_str())]);
+            self.event(Event::Empty(el))?;
+        }
+        self.end(name)
+    }
+
+    fn start(&mut self, name: &[u8]) -> Result<()> {
+        self.event(Event::Start(BytesStart::borrowed_name(name)))
+    }
+
+    fn end(&mut self, name: &[u8]) -> Result<()> {
+        self.event(Event::End(BytesEnd::borrowed(name)))
+    }
+
+    fn text(&mut self, text: &str) -> Result<()> {
+        self.event(Event::Text(BytesText::from_plain_str(text)))
+    }
+
+    fn newline(&mut self) -> Result<()> {
+        self.event(Event::Text(BytesText::from_escaped_str("\n")))
+    }
+
+    fn event(&mut self, event: Event) -> Result<()> {
+        Ok(self.writer.write_event(event)?)
+    }
+}
+
+impl Dict {
+    /// записывает словарь в XML-файл в формате opencorpora.org.
+    ///
+    /// Записанный файл читается [`read_from_xml`](#method.read_from_xml)
+    /// в словарь, равный исходному.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use std::io::BufWriter;
+    ///
+    /// use opencorpora::Dict;
+    ///
+    /// let dict = Dict::read_from_xml(File::open("dict.xml").unwrap()).unwrap();
+    /// let out = BufWriter::new(File::create("dict.fixed.xml").unwrap());
+    /// dict.write_xml(out).unwrap();
+    /// ```
+    pub fn write_xml<W: Write>(&self, w: W) -> Result<()> {
+        let mut writer = DictWriter { writer: Writer::new(w) };
+        writer.write_dict(self)?;
+        writer.writer.inner().flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+mod common;
+
+use opencorpora::MorphAnalyzer;
+
+use common::load_dict;
+
+#[test]
+fn test_parse() {
+    let dict = load_dict();
+    let analyzer = MorphAnalyzer::new(&dict);
+
+    let mut parses: Vec<_> = analyzer
+        .parse("Стали")
+        .into_iter()
+        .map(|p| (p.normal_form, p.tag.to_string(), p.lemma_id, p.form_index))
+        .collect();
+    parses.sort();
+    assert_eq!(parses.len(), 6);
+    assert_eq!(parses[0], ("стал", "VERB,perf plur,past".to_owned(), 4, 3));
+    assert_eq!(parses[1], ("сталь", "NOUN,inan,femn plur,accs".to_owned(), 2, 9));
+
+    assert!(analyzer.parse("нетслова").is_empty());
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use std::sync::Arc;
+
+use opencorpora::error::Error;
+use opencorpora::{Dict, Grammeme, GrammemeSet};
+
+use common::load_dict;
+
+#[test]
+fn test_binary_round_trip() {
+    let dict = load_dict();
+
+    let mut data = Vec::new();
+    dict.save_binary(&mut data).unwrap();
+    let loaded = Dict::load_binary(data.as_slice()).unwrap();
+
+    assert_eq!(loaded, dict);
+    assert_eq!(loaded.lookup("файлов").count(), 1);
+}
+
+#[test]
+fn test_binary_corruption_is_detected() {
+    let dict = load_dict();
+    let mut data = Vec::new();
+    dict.save_binary(&mut data).unwrap();
+
+    let mut corrupted = data.clone();
+    let middle = corrupted.len() / 2;
+    corrupted[middle] ^= 0xff;
+    assert!(Dict::load_binary(corrupted.as_slice()).is_err());
+
+    assert!(Dict::load_binary(&data[..data.len() - 1]).is_err());
+    assert!(Dict::load_binary(&b"<?xml"[..]).is_err());
+}
+
+#[test]
+fn test_binary_too_many_grammemes() {
+    let mut dict = Dict::default();
+    for index in 0..=GrammemeSet::CAPACITY {
+        dict.grammemes.push(Arc::new(Grammeme {
+            name: format!("G{}", index),
+            index,
+            ..Grammeme::default()
+        }));
+    }
+    let mut data = Vec::new();
+    dict.save_binary(&mut data).unwrap();
+
+    match Dict::load_binary(data.as_slice()) {
+        Err(Error::TooManyGrammemes) => {}
+        r => panic!("unexpected result: {:?}", r.map(|d| d.grammemes.len())),
+    }
+}
@@ -0,0 +1,16 @@
+//! Общие для интеграционных тестов путь к тестовому словарю и его загрузка.
+
+// каждый тест использует только часть этого модуля
+#![allow(dead_code)]
+
+use std::fs::File;
+
+use opencorpora::Dict;
+
+/// Путь к тестовому словарю
+pub const DICT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/dict.xml");
+
+/// читает тестовый словарь.
+pub fn load_dict() -> Dict {
+    Dict::read_from_xml(File::open(DICT_PATH).unwrap()).unwrap()
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use std::sync::Arc;
+use std::thread;
+
+use opencorpora::error::Error;
+use opencorpora::{MorphAnalyzer, Tag};
+
+use common::load_dict;
+
+#[test]
+fn test_dict_is_shared_between_threads() {
+    let dict = Arc::new(load_dict());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let dict = dict.clone();
+            thread::spawn(move || dict.lemmata.iter().map(|l| l.forms.len()).sum::<usize>())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 40);
+    }
+}
+
+#[test]
+fn test_lookup() {
+    let dict = load_dict();
+
+    let mut found: Vec<_> = dict
+        .lookup("стали")
+        .map(|(lemma, form)| {
+            let grammemes: Vec<_> = form.grammemes.iter().map(|g| g.name.as_str()).collect();
+            (lemma.id, grammemes.join(","))
+        })
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        vec![
+            (2, "plur,accs".to_owned()),
+            (2, "plur,nomn".to_owned()),
+            (2, "sing,datv".to_owned()),
+            (2, "sing,gent".to_owned()),
+            (2, "sing,loct".to_owned()),
+            (4, "plur,past".to_owned()),
+        ]
+    );
+
+    assert_eq!(dict.lookup("файлов").count(), 1);
+    assert_eq!(dict.lookup("нет такого слова").count(), 0);
+}
+
+#[test]
+fn test_lookup_with_stale_index() {
+    let mut dict = load_dict();
+    dict.lemmata.remove(0);
+    dict.lemmata.pop();
+
+    // устаревший индекс не должен приводить к панике или к чужим словоформам
+    assert!(dict.lookup("стали").all(|(lemma, form)| form.word == "стали" && lemma.id != 1));
+    assert_eq!(dict.lookup("стальной").count(), 0);
+    assert!(MorphAnalyzer::new(&dict).parse("стальной").is_empty());
+
+    dict.reindex().unwrap();
+    assert_eq!(dict.lookup("стали").count(), 6);
+    assert_eq!(dict.lookup("файл").count(), 0);
+}
+
+#[test]
+fn test_lookup_by_id_and_name() {
+    let dict = load_dict();
+
+    assert_eq!(dict.lemma(5).unwrap().word, "ставший");
+    assert!(dict.lemma(100).is_none());
+    assert_eq!(dict.lemma_with_revision(2, 2).unwrap().word, "сталь");
+    match dict.lemma_with_revision(2, 1) {
+        Err(Error::RevisionMismatch { id: 2, expected: 1, actual: 2 }) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    match dict.lemma_with_revision(100, 1) {
+        Err(Error::UnknownLemma(100)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    assert_eq!(dict.link_kind(2).unwrap().name, "INFN-PRTF");
+    assert!(dict.link_kind(4).is_none());
+
+    let grammeme = dict.grammeme("gent").unwrap();
+    assert_eq!(grammeme.alias, "рд");
+    assert!(Arc::ptr_eq(grammeme, &dict.grammemes[grammeme.index]));
+    assert!(dict.grammeme("рд").is_none());
+}
+
+#[test]
+fn test_lookup_by_id_and_name_with_stale_index() {
+    let mut dict = load_dict();
+    dict.lemmata.pop();
+    dict.lemmata.swap(0, 1);
+    dict.link_kinds.clear();
+    let grammemes = std::mem::take(&mut dict.grammemes);
+
+    assert!(dict.lemma(7).is_none());
+    assert!(dict.lemma(1).is_none());
+    assert!(matches!(dict.lemma_with_revision(7, 7), Err(Error::UnknownLemma(7))));
+    assert!(dict.link_kind(2).is_none());
+    assert!(dict.grammeme("gent").is_none());
+    assert!(dict.grammeme_set(["gent"]).is_err());
+    assert!(Tag::parse(&dict, "NOUN,inan,masc sing,gent").is_err());
+
+    // граммемы ограничений и лексем должны быть в словаре
+    assert!(matches!(dict.reindex(), Err(Error::UnknownGrammeme(_))));
+    dict.grammemes = grammemes;
+    dict.reindex().unwrap();
+    assert_eq!(dict.lemma(1).unwrap().word, "файл");
+    assert!(dict.lemma(7).is_none());
+}
+
+#[test]
+fn test_reindex_keeps_shared_lemmata() {
+    let mut dict = load_dict();
+    let lemmata = dict.lemmata.clone();
+    dict.reindex().unwrap();
+
+    // согласованные со словарём лексемы не копируются
+    assert!(dict.lemmata.iter().zip(&lemmata).all(|(a, b)| Arc::ptr_eq(a, b)));
+    for link in &dict.links {
+        assert!(Arc::ptr_eq(&link.from, dict.lemma(link.from.id).unwrap()));
+        assert!(Arc::ptr_eq(&link.to, dict.lemma(link.to.id).unwrap()));
+        assert!(Arc::ptr_eq(&link.kind, dict.link_kind(link.kind.id).unwrap()));
+    }
+}
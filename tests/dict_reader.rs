@@ -0,0 +1,72 @@
+mod common;
+
+use std::fs::File;
+use std::io::BufReader;
+
+use opencorpora::DictReader;
+
+use common::{load_dict, DICT_PATH};
+
+#[test]
+fn test_streaming_matches_read_from_xml() {
+    let dict = load_dict();
+
+    let file = File::open(DICT_PATH).unwrap();
+    let mut reader = DictReader::new(BufReader::new(file)).unwrap();
+    assert_eq!(reader.version(), dict.version);
+    assert_eq!(reader.revision(), dict.revision);
+    assert_eq!(reader.grammemes().unwrap().len(), dict.grammemes.len());
+    assert_eq!(reader.restrictions().unwrap().len(), dict.restrictions.len());
+
+    let lemmata: Vec<_> = reader.lemmata().collect::<Result<_, _>>().unwrap();
+    assert_eq!(lemmata.len(), dict.lemmata.len());
+    for (streamed, loaded) in lemmata.iter().zip(&dict.lemmata) {
+        assert_eq!(streamed.id, loaded.id);
+        assert_eq!(streamed.word, loaded.word);
+        assert_eq!(streamed.forms.len(), loaded.forms.len());
+    }
+
+    assert_eq!(reader.link_kinds().unwrap().len(), dict.link_kinds.len());
+    let links: Vec<_> = reader.links().collect::<Result<_, _>>().unwrap();
+    assert_eq!(links.len(), dict.links.len());
+    for (streamed, loaded) in links.iter().zip(&dict.links) {
+        assert_eq!(streamed.from, loaded.from.id);
+        assert_eq!(streamed.to, loaded.to.id);
+        assert_eq!(streamed.kind.name, loaded.kind.name);
+    }
+    reader.finish().unwrap();
+}
+
+#[test]
+fn test_skipping_sections() {
+    let file = File::open(DICT_PATH).unwrap();
+    let mut reader = DictReader::new(BufReader::new(file)).unwrap();
+    assert_eq!(reader.link_kinds().unwrap().len(), 3);
+    assert_eq!(reader.grammemes().unwrap().len(), 30);
+    assert_eq!(reader.lemmata().count(), 0);
+    assert_eq!(reader.links().count(), 3);
+    reader.finish().unwrap();
+}
+
+#[test]
+fn test_skipped_lemmata_are_not_parsed() {
+    let xml = std::fs::read_to_string(DICT_PATH).unwrap().replacen(
+        r#"<l t="сталь"><g v="NOUN"/>"#,
+        r#"<l t="сталь"><g v="NUON"/>"#,
+        1,
+    );
+    let mut reader = DictReader::new(xml.as_bytes()).unwrap();
+    assert_eq!(reader.link_kinds().unwrap().len(), 3);
+    assert_eq!(reader.lemmata().count(), 0);
+    assert_eq!(reader.links().count(), 3);
+    reader.finish().unwrap();
+
+    // частично прочитанный раздел лексем тоже пропускается без разбора
+    let mut reader = DictReader::new(xml.as_bytes()).unwrap();
+    assert_eq!(reader.lemmata().next().unwrap().unwrap().id, 1);
+    assert_eq!(reader.links().count(), 3);
+    reader.finish().unwrap();
+
+    let mut reader = DictReader::new(xml.as_bytes()).unwrap();
+    assert!(reader.lemmata().any(|lemma| lemma.is_err()));
+}
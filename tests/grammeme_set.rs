@@ -0,0 +1,154 @@
+mod common;
+
+use std::sync::Arc;
+
+use opencorpora::error::Error;
+use opencorpora::{Dict, Form, Grammeme, GrammemeSet, Lemma};
+
+use common::load_dict;
+
+#[test]
+fn test_grammeme_set_operations() {
+    let dict = load_dict();
+    for (idx, grammeme) in dict.grammemes.iter().enumerate() {
+        assert_eq!(grammeme.index, idx);
+    }
+
+    let plur_gent = dict.grammeme_set(["plur", "gent"]).unwrap();
+    let plur = dict.grammeme_set(["plur"]).unwrap();
+    let sing_gent = dict.grammeme_set(["sing", "gent"]).unwrap();
+    let gent = dict.grammeme_set(["gent"]).unwrap();
+
+    assert_eq!(plur_gent.len(), 2);
+    assert!(plur_gent.contains(&dict.grammemes[16]));
+    assert!(!plur_gent.contains(&dict.grammemes[15]));
+    assert_eq!(plur_gent.intersection(&sing_gent), gent);
+    assert_eq!(plur_gent.difference(&gent), plur);
+    assert_eq!(plur | gent, plur_gent);
+    assert_eq!((plur_gent | sing_gent).len(), 3);
+    assert!(plur.is_subset(&plur_gent));
+    assert!(plur_gent.is_superset(&gent));
+    assert!(plur.is_disjoint(&sing_gent));
+    assert!(GrammemeSet::new().is_empty());
+
+    assert!(dict.grammeme_set(["plur", "nope"]).is_err());
+
+    let lemma = &dict.lemmata[0];
+    let found: Vec<_> = lemma
+        .forms
+        .iter()
+        .filter(|f| (lemma.grammeme_set() | f.grammeme_set()).is_superset(&plur_gent))
+        .map(|f| f.word.as_str())
+        .collect();
+    assert_eq!(found, ["файлов"]);
+}
+
+#[test]
+fn test_grammeme_sets_follow_public_fields() {
+    let dict = load_dict();
+    let loaded = &dict.lemmata[0];
+    let wanted = dict.grammeme_set(["datv", "plur"]).unwrap();
+
+    let built = Lemma {
+        id: loaded.id,
+        revision: loaded.revision,
+        word: loaded.word.clone(),
+        grammemes: loaded.grammemes.clone(),
+        forms: loaded
+            .forms
+            .iter()
+            .map(|form| Form {
+                word: form.word.clone(),
+                grammemes: form.grammemes.clone(),
+            })
+            .collect(),
+    };
+    assert_eq!(built, **loaded);
+    assert_eq!(built.inflect(&wanted).unwrap().word, "файлам");
+
+    let mut changed = (**loaded).clone();
+    for form in &mut changed.forms {
+        if form.word == "файлам" {
+            form.grammemes.clear();
+        }
+    }
+    assert!(changed.inflect(&wanted).is_none());
+}
+
+fn grammeme(name: &str) -> Grammeme {
+    Grammeme {
+        name: name.to_owned(),
+        ..Grammeme::default()
+    }
+}
+
+#[test]
+fn test_grammeme_sets_of_dict_built_in_code() {
+    // граммеме вне словаря позиция не назначена
+    assert_eq!(grammeme("A").index, usize::MAX);
+    assert!(!GrammemeSet::new().insert(&grammeme("A")));
+
+    let mut dict = Dict::default();
+    dict.grammemes = ["A", "B", "C"].iter().map(|&name| Arc::new(grammeme(name))).collect();
+    // граммемы лексемы не обязаны совпадать по указателю с граммемами словаря
+    dict.lemmata.push(Arc::new(Lemma {
+        id: 1,
+        grammemes: vec![Arc::new(grammeme("A"))],
+        forms: vec![Form {
+            word: String::new(),
+            grammemes: vec![Arc::new(grammeme("C"))],
+        }],
+        ..Lemma::default()
+    }));
+    dict.reindex().unwrap();
+
+    for (idx, grammeme) in dict.grammemes.iter().enumerate() {
+        assert_eq!(grammeme.index, idx);
+    }
+    let a = dict.grammeme_set(["A"]).unwrap();
+    assert!(a.contains(dict.grammeme("A").unwrap()));
+    assert!(!a.contains(dict.grammeme("B").unwrap()));
+    let lemma = &dict.lemmata[0];
+    assert_eq!(lemma.grammeme_set(), a);
+    assert_eq!(lemma.forms[0].grammeme_set(), dict.grammeme_set(["C"]).unwrap());
+    assert!(Arc::ptr_eq(&lemma.grammemes[0], &dict.grammemes[0]));
+
+    dict.lemmata.push(Arc::new(Lemma::default()));
+    Arc::make_mut(&mut dict.lemmata[1]).grammemes.push(Arc::new(grammeme("D")));
+    match dict.reindex() {
+        Err(Error::UnknownGrammeme(name)) => assert_eq!(name, "D"),
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn test_too_many_grammemes() {
+    let mut dict = Dict::default();
+    dict.grammemes = (0..=GrammemeSet::CAPACITY)
+        .map(|idx| Arc::new(grammeme(&format!("G{}", idx))))
+        .collect();
+    match dict.reindex() {
+        Err(Error::TooManyGrammemes) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let mut xml = String::from(r#"<dictionary version="0.92" revision="1"><grammemes>"#);
+    for idx in 0..=GrammemeSet::CAPACITY {
+        xml.push_str(&format!(r#"<grammeme parent=""><name>G{}</name></grammeme>"#, idx));
+    }
+    xml.push_str("</grammemes></dictionary>");
+    match Dict::read_from_xml(xml.as_bytes()) {
+        Err(Error::Located(e)) => assert!(matches!(e.source, Error::TooManyGrammemes)),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let outside = Grammeme {
+        index: GrammemeSet::CAPACITY,
+        ..grammeme("G256")
+    };
+    let mut set = GrammemeSet::new();
+    assert!(!set.insert(&outside));
+    assert!(!set.remove(&outside));
+    assert!(!set.contains(&outside));
+    assert!(set.is_empty());
+}
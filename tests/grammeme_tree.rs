@@ -0,0 +1,59 @@
+mod common;
+
+use std::sync::Arc;
+
+use opencorpora::error::Error;
+use opencorpora::{Dict, Grammeme, GrammemeTree, ParsingState};
+
+use common::load_dict;
+
+fn names<'a, I: Iterator<Item = &'a Arc<Grammeme>>>(grammemes: I) -> Vec<&'a str> {
+    grammemes.map(|g| g.name.as_str()).collect()
+}
+
+#[test]
+fn test_grammeme_tree_navigation() {
+    let dict = load_dict();
+    let tree = dict.grammeme_tree().unwrap();
+
+    assert_eq!(tree.parent("nomn").unwrap().name, "CAse");
+    assert!(tree.parent("CAse").is_none());
+    assert_eq!(names(tree.children("NMbr")), ["sing", "plur"]);
+    assert_eq!(names(tree.ancestors("nomn")), ["CAse"]);
+    assert_eq!(names(tree.descendants("ASpc")), ["perf", "impf"]);
+    assert_eq!(tree.depth("POST"), Some(0));
+    assert_eq!(tree.depth("NOUN"), Some(1));
+    assert_eq!(tree.depth("nope"), None);
+    assert!(tree.is_a("nomn", "CAse"));
+    assert!(tree.is_a("CAse", "CAse"));
+    assert!(!tree.is_a("nomn", "NMbr"));
+    assert!(names(tree.roots()).contains(&"POST"));
+}
+
+fn grammeme(name: &str, parent: Option<&str>) -> Arc<Grammeme> {
+    Arc::new(Grammeme {
+        name: name.to_owned(),
+        parent: parent.map(|p| p.to_owned()),
+        ..Grammeme::default()
+    })
+}
+
+#[test]
+fn test_grammeme_tree_errors() {
+    let dangling = [grammeme("CAse", None), grammeme("nomn", Some("Case"))];
+    assert!(matches!(GrammemeTree::new(&dangling), Err(Error::GrammemeTree(_))));
+
+    let cycle = [grammeme("A", Some("B")), grammeme("B", Some("A"))];
+    assert!(matches!(GrammemeTree::new(&cycle), Err(Error::GrammemeTree(_))));
+
+    let xml = r#"<dictionary version="0.92" revision="1"><grammemes>
+        <grammeme parent="CAse"><name>nomn</name><alias>им</alias><description>им</description></grammeme>
+        </grammemes></dictionary>"#;
+    match Dict::read_from_xml(xml.as_bytes()) {
+        Err(Error::Located(e)) => {
+            assert!(matches!(e.source, Error::GrammemeTree(_)));
+            assert_eq!(e.state, ParsingState::Grammemes);
+        }
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
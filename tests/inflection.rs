@@ -0,0 +1,55 @@
+mod common;
+
+use opencorpora::{Dict, Lemma};
+
+use common::load_dict;
+
+fn find_lemma<'a>(dict: &'a Dict, word: &str) -> &'a Lemma {
+    dict.lemmata.iter().find(|l| l.word == word).unwrap()
+}
+
+#[test]
+fn test_inflect() {
+    let dict = load_dict();
+    let lemma = find_lemma(&dict, "файл");
+    let set = |names: &[&str]| dict.grammeme_set(names.iter().copied()).unwrap();
+
+    assert_eq!(lemma.inflect(&set(&["datv", "plur"])).unwrap().word, "файлам");
+    assert_eq!(lemma.inflect(&set(&["NOUN", "ablt", "sing"])).unwrap().word, "файлом");
+    // неоднозначный запрос: выбирается первая подходящая словоформа
+    assert_eq!(lemma.inflect(&set(&["plur"])).unwrap().word, "файлы");
+    assert!(lemma.inflect(&set(&["VERB"])).is_none());
+    assert!(lemma.inflect(&set(&["nomn", "past"])).is_none());
+}
+
+#[test]
+fn test_make_agree_with_number() {
+    let dict = load_dict();
+
+    let lemma = find_lemma(&dict, "файл");
+    let nomn = &lemma.forms[0];
+    let agree = |num| lemma.make_agree_with_number(nomn, num).unwrap().word.as_str();
+    assert_eq!(agree(1), "файл");
+    assert_eq!(agree(2), "файла");
+    assert_eq!(agree(5), "файлов");
+    assert_eq!(agree(11), "файлов");
+    assert_eq!(agree(14), "файлов");
+    assert_eq!(agree(21), "файл");
+    assert_eq!(agree(23), "файла");
+    assert_eq!(agree(0), "файлов");
+
+    let datv = &lemma.forms[2];
+    assert_eq!(lemma.make_agree_with_number(datv, 1).unwrap().word, "файлу");
+    assert_eq!(lemma.make_agree_with_number(datv, 5).unwrap().word, "файлам");
+
+    let lemma = find_lemma(&dict, "стальной");
+    let femn = &lemma.forms[2];
+    assert_eq!(lemma.make_agree_with_number(femn, 1).unwrap().word, "стальная");
+    assert_eq!(lemma.make_agree_with_number(femn, 2).unwrap().word, "стальные");
+    assert_eq!(lemma.make_agree_with_number(femn, 5).unwrap().word, "стальных");
+    let masc = &lemma.forms[0];
+    assert_eq!(lemma.make_agree_with_number(masc, 3).unwrap().word, "стальных");
+
+    let lemma = find_lemma(&dict, "стал");
+    assert!(lemma.make_agree_with_number(&lemma.forms[0], 2).is_none());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use std::fs;
+use std::io::BufReader;
+use std::sync::{Arc, Mutex};
+
+use opencorpora::{Dict, DictReader, Lemma, ParseOptions};
+
+use common::{load_dict, DICT_PATH};
+
+fn has_grammeme(lemma: &Lemma, name: &str) -> bool {
+    lemma.grammemes.iter().any(|g| g.name == name)
+}
+
+#[test]
+fn test_filter_sees_lemma_header() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    let mut options = ParseOptions::default();
+    options.lemma_filter = Some(Arc::new(move |lemma: &Lemma| {
+        log.lock().unwrap().push((lemma.id, lemma.word.clone(), lemma.grammemes.len(), lemma.forms.len()));
+        true
+    }));
+    let expected = load_dict();
+    let file = fs::File::open(DICT_PATH).unwrap();
+    let (dict, _) = Dict::read_from_xml_with_options(file, &options).unwrap();
+    assert_eq!(dict, expected);
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), expected.lemmata.len());
+    for (&(id, ref word, grammemes, forms), lemma) in seen.iter().zip(&expected.lemmata) {
+        assert_eq!(id, lemma.id);
+        assert_eq!(word, &lemma.word);
+        assert_eq!(grammemes, lemma.grammemes.len());
+        assert_eq!(forms, 0);
+    }
+}
+
+#[test]
+fn test_filter_drops_lemmata_and_their_links() {
+    let expected = load_dict();
+    let mut options = ParseOptions::default();
+    options.lemma_filter = Some(Arc::new(|lemma: &Lemma| !has_grammeme(lemma, "PRTF")));
+    let file = fs::File::open(DICT_PATH).unwrap();
+    let (dict, diagnostics) = Dict::read_from_xml_with_options(file, &options).unwrap();
+    assert!(diagnostics.is_empty());
+    let ids: Vec<_> = dict.lemmata.iter().map(|l| l.id).collect();
+    assert_eq!(ids, [1, 2, 3, 4, 6, 7]);
+    assert_eq!(dict.lemma(3), expected.lemma(3));
+    let ids: Vec<_> = dict.links.iter().map(|l| l.id).collect();
+    assert_eq!(ids, [1, 3]);
+
+    let mut options = ParseOptions::default();
+    options.lemma_filter = Some(Arc::new(|lemma: &Lemma| has_grammeme(lemma, "NOUN")));
+    let file = fs::File::open(DICT_PATH).unwrap();
+    let (dict, _) = Dict::read_from_xml_with_options(file, &options).unwrap();
+    let words: Vec<_> = dict.lemmata.iter().map(|l| l.word.as_str()).collect();
+    assert_eq!(words, ["файл", "сталь"]);
+    assert!(dict.links.is_empty());
+}
+
+#[test]
+fn test_reader_filter() {
+    let mut options = ParseOptions::default();
+    options.lemma_filter = Some(Arc::new(|lemma: &Lemma| lemma.id <= 4));
+    let file = BufReader::new(fs::File::open(DICT_PATH).unwrap());
+    let mut reader = DictReader::with_options(file, options).unwrap();
+    let ids: Vec<_> = reader.lemmata().map(|l| l.unwrap().id).collect();
+    assert_eq!(ids, [1, 2, 3, 4]);
+    let ids: Vec<_> = reader.links().map(|l| l.unwrap().id).collect();
+    assert_eq!(ids, [1]);
+    reader.finish().unwrap();
+}
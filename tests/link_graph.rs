@@ -0,0 +1,38 @@
+mod common;
+
+use opencorpora::Direction;
+
+use common::load_dict;
+
+#[test]
+fn test_link_graph() {
+    let dict = load_dict();
+    let graph = dict.link_graph();
+
+    let targets: Vec<_> = graph.outgoing(3).map(|link| link.to.id).collect();
+    assert_eq!(targets, [4, 5, 6]);
+    assert_eq!(graph.outgoing(4).count(), 0);
+    assert_eq!(graph.outgoing(100).count(), 0);
+
+    let sources: Vec<_> = graph.incoming(5).map(|link| link.from.id).collect();
+    assert_eq!(sources, [3]);
+
+    let infn_grnd = dict.link_kinds.iter().find(|k| k.name == "INFN-GRND").unwrap();
+    let gerunds: Vec<_> = graph.outgoing_of_kind(3, infn_grnd).map(|link| link.to.word.as_str()).collect();
+    assert_eq!(gerunds, ["став"]);
+    assert_eq!(graph.incoming_of_kind(5, infn_grnd).count(), 0);
+
+    let derived: Vec<_> = graph
+        .reachable(3, Direction::Outgoing, |kind| kind.name == "INFN-PRTF" || kind.name == "INFN-GRND")
+        .into_iter()
+        .map(|lemma| lemma.word.as_str())
+        .collect();
+    assert_eq!(derived, ["ставший", "став"]);
+
+    let infinitives: Vec<_> = graph
+        .reachable(6, Direction::Incoming, |_| true)
+        .into_iter()
+        .map(|lemma| lemma.id)
+        .collect();
+    assert_eq!(infinitives, [3]);
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "mmap")]
+
+mod common;
+
+use std::fs::File;
+
+use opencorpora::error::Error;
+use opencorpora::MappedDict;
+
+use common::load_dict;
+
+#[test]
+fn test_mapped_dict_matches_loaded_dict() {
+    let dict = load_dict();
+    let path = std::env::temp_dir().join(format!("opencorpora-mapped-{}.bin", std::process::id()));
+    dict.save_binary(File::create(&path).unwrap()).unwrap();
+
+    let mapped = MappedDict::open(&path).unwrap();
+    mapped.verify().unwrap();
+    assert_eq!(mapped.version(), dict.version);
+    assert_eq!(mapped.revision(), dict.revision);
+    assert_eq!(mapped.grammemes(), dict.grammemes.as_slice());
+    assert_eq!(mapped.restrictions(), dict.restrictions.as_slice());
+    assert_eq!(mapped.link_kinds(), dict.link_kinds.as_slice());
+
+    assert_eq!(mapped.lemmata_len(), dict.lemmata.len());
+    for (lemma, expected) in mapped.lemmata().zip(&dict.lemmata) {
+        let lemma = lemma.unwrap();
+        assert_eq!(lemma.id(), expected.id);
+        assert_eq!(lemma.word(), expected.word);
+        assert!(lemma.grammemes().eq(expected.grammemes.iter()));
+        assert_eq!(lemma.grammeme_set(), expected.grammeme_set());
+        assert_eq!(lemma.forms_len(), expected.forms.len());
+        for (form, expected) in lemma.forms().zip(&expected.forms) {
+            assert_eq!(form.word(), expected.word);
+            assert!(form.grammemes().eq(expected.grammemes.iter()));
+            assert_eq!(form.grammeme_set(), expected.grammeme_set());
+        }
+        assert_eq!(&lemma.to_lemma().unwrap(), &**expected);
+    }
+
+    assert_eq!(mapped.links_len(), dict.links.len());
+    for (link, expected) in mapped.links().zip(&dict.links) {
+        let link = link.unwrap();
+        assert_eq!(link.id(), expected.id);
+        assert_eq!(link.from().unwrap().id(), expected.from.id);
+        assert_eq!(link.to().unwrap().id(), expected.to.id);
+        assert_eq!(link.kind(), &expected.kind);
+    }
+    assert!(mapped.lemma(mapped.lemmata_len()).is_err());
+
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_mapped_dict_checksum() {
+    let dict = load_dict();
+    let mut data = Vec::new();
+    dict.save_binary(&mut data).unwrap();
+    let last = data.len() - 1;
+    data[last] ^= 0xff;
+    let path = std::env::temp_dir().join(format!("opencorpora-checksum-{}.bin", std::process::id()));
+    std::fs::write(&path, &data).unwrap();
+
+    // контрольная сумма проверяется только по запросу
+    let mapped = MappedDict::open(&path).unwrap();
+    assert_eq!(mapped.lemmata_len(), dict.lemmata.len());
+    match mapped.verify() {
+        Err(Error::Binary(message)) => assert_eq!(message, "checksum mismatch"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use opencorpora::Dict;
+
+use common::{load_dict, DICT_PATH};
+
+/// записывает сжатую копию тестового словаря во временный каталог.
+fn compressed_copy<F>(name: &str, compress: F) -> PathBuf
+where
+    F: FnOnce(&[u8]) -> Vec<u8>,
+{
+    let path = std::env::temp_dir().join(format!("opencorpora-{}-{}", std::process::id(), name));
+    fs::write(&path, compress(&fs::read(DICT_PATH).unwrap())).unwrap();
+    path
+}
+
+fn bzip2(data: &[u8]) -> Vec<u8> {
+    let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_open_plain() {
+    let expected = load_dict();
+    assert_eq!(Dict::open(DICT_PATH).unwrap(), expected);
+}
+
+#[test]
+fn test_open_plain_with_compressed_extension() {
+    let expected = load_dict();
+    // несжатый файл читается как есть, даже если расширение говорит об обратном
+    let path = compressed_copy("dict.xml.bz2", |data| data.to_vec());
+    let dict = Dict::open(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(dict.unwrap(), expected);
+}
+
+#[cfg(feature = "bzip2")]
+#[test]
+fn test_open_bzip2() {
+    let expected = Dict::open(DICT_PATH).unwrap();
+    // расширение не совпадает с форматом: сжатие определяется по первым байтам
+    let path = compressed_copy("dict.xml", bzip2);
+    let dict = Dict::open(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(dict.unwrap(), expected);
+}
+
+#[cfg(not(feature = "bzip2"))]
+#[test]
+fn test_open_bzip2_disabled() {
+    use opencorpora::error::Error;
+
+    let path = compressed_copy("dict.xml.bz2", bzip2);
+    let dict = Dict::open(&path);
+    fs::remove_file(&path).unwrap();
+    match dict {
+        Err(Error::UnsupportedCompression { feature: "bzip2", .. }) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_open_gzip() {
+    let expected = Dict::open(DICT_PATH).unwrap();
+    let path = compressed_copy("dict.xml.gz", |data| {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    });
+    let dict = Dict::open(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(dict.unwrap(), expected);
+}
+
+#[cfg(feature = "xz")]
+#[test]
+fn test_open_xz() {
+    let expected = Dict::open(DICT_PATH).unwrap();
+    let path = compressed_copy("dict.xml.xz", |data| {
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    });
+    let dict = Dict::open(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(dict.unwrap(), expected);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_open_zstd() {
+    let expected = Dict::open(DICT_PATH).unwrap();
+    let path = compressed_copy("dict.xml.zst", |data| zstd::encode_all(data, 0).unwrap());
+    let dict = Dict::open(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(dict.unwrap(), expected);
+}
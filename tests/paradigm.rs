@@ -0,0 +1,43 @@
+mod common;
+
+use std::sync::Arc;
+
+use opencorpora::{Lemma, ParadigmTable};
+
+use common::load_dict;
+
+fn words(lemma: &Lemma) -> Vec<(String, Vec<String>)> {
+    lemma
+        .forms
+        .iter()
+        .map(|f| (f.word.clone(), f.grammemes.iter().map(|g| g.name.clone()).collect()))
+        .collect()
+}
+
+#[test]
+fn test_paradigms_rebuild_lemmata() {
+    let mut dict = load_dict();
+
+    // лексема с той же парадигмой, что и «файл»
+    let mut copy = (*dict.lemmata[0]).clone();
+    copy.id = 100;
+    copy.word = "стол".to_owned();
+    for form in &mut copy.forms {
+        form.word = form.word.replacen("файл", "стол", 1);
+    }
+    dict.lemmata.push(Arc::new(copy));
+
+    let table = ParadigmTable::new(&dict);
+    assert_eq!(table.lemmata.len(), dict.lemmata.len());
+    // «стать» и «став» тоже совпадают: одна словоформа без граммем
+    assert_eq!(table.paradigms.len(), dict.lemmata.len() - 2);
+    assert_eq!(table.lemmata[0].stem, "файл");
+    assert_eq!(table.lemmata[0].paradigm, table.lemmata[7].paradigm);
+
+    for (compact, lemma) in table.lemmata.iter().zip(&dict.lemmata) {
+        let rebuilt = table.lemma(compact);
+        assert_eq!(rebuilt.id, lemma.id);
+        assert_eq!(rebuilt.word, lemma.word);
+        assert_eq!(words(&rebuilt), words(lemma));
+    }
+}
@@ -0,0 +1,180 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use opencorpora::error::Error;
+use opencorpora::{Dict, Lemma, ParseOptions};
+
+use common::DICT_PATH;
+
+/// размножает лексемы тестового словаря, чтобы раздел лексем делился на несколько частей.
+fn large_dict_xml() -> String {
+    let xml = fs::read_to_string(DICT_PATH).unwrap();
+    let mut out = String::new();
+    for line in xml.lines() {
+        out.push_str(line);
+        out.push('\n');
+        if line == "<lemmata>" {
+            for copy in 1..=400 {
+                for lemma in xml.lines().filter(|l| l.starts_with("<lemma ")) {
+                    let id: usize = lemma["<lemma id=\"".len()..].split('"').next().unwrap().parse().unwrap();
+                    let new_id = copy * 10 + id;
+                    out.push_str(&lemma.replacen(
+                        &format!(r#"<lemma id="{}" rev="{}">"#, id, id),
+                        &format!(r#"<lemma id="{}" rev="{}">"#, new_id, new_id),
+                        1,
+                    ));
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    out
+}
+
+fn lenient() -> ParseOptions {
+    let mut options = ParseOptions::default();
+    options.strict = false;
+    options
+}
+
+fn assert_same_result(xml: &str, options: &ParseOptions) {
+    let sequential = Dict::read_from_xml_with_options(xml.as_bytes(), options);
+    let parallel = Dict::read_from_xml_parallel(xml.as_bytes(), options);
+    match (sequential, parallel) {
+        (Ok((expected, expected_diagnostics)), Ok((dict, diagnostics))) => {
+            assert_eq!(dict, expected);
+            let expected_diagnostics: Vec<_> = expected_diagnostics.iter().map(|d| d.to_string()).collect();
+            let diagnostics: Vec<_> = diagnostics.iter().map(|d| d.to_string()).collect();
+            assert_eq!(diagnostics, expected_diagnostics);
+        }
+        (Err(expected), Err(e)) => assert_eq!(e.to_string(), expected.to_string()),
+        (expected, result) => panic!(
+            "results differ: {:?} vs {:?}",
+            expected.map(|(d, _)| d.lemmata.len()),
+            result.map(|(d, _)| d.lemmata.len())
+        ),
+    }
+}
+
+#[test]
+fn test_parallel_matches_sequential() {
+    let xml = large_dict_xml();
+    assert_same_result(&xml, &ParseOptions::default());
+
+    let (dict, _) = Dict::read_from_xml_parallel(xml.as_bytes(), &ParseOptions::default()).unwrap();
+    assert_eq!(dict.lemmata.len(), 7 * 401);
+    let ids: Vec<_> = dict.lemmata.iter().map(|l| l.id).collect();
+    assert_eq!(ids[..8], [11, 12, 13, 14, 15, 16, 17, 21]);
+    assert_eq!(ids[ids.len() - 7..], [1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(dict.links.len(), 3);
+
+    let small = fs::read_to_string(DICT_PATH).unwrap();
+    assert_same_result(&small, &ParseOptions::default());
+}
+
+#[test]
+fn test_parallel_with_options() {
+    let xml = large_dict_xml();
+
+    let mut options = ParseOptions::default();
+    options.lemma_filter = Some(Arc::new(|lemma: &Lemma| lemma.id % 10 != 5 && lemma.id % 10 != 3));
+    assert_same_result(&xml, &options);
+
+    let mut options = ParseOptions::default();
+    options.restrictions = false;
+    options.links = false;
+    assert_same_result(&xml, &options);
+
+    let mut options = ParseOptions::default();
+    options.lemmata = false;
+    assert_same_result(&xml, &options);
+}
+
+#[test]
+fn test_parallel_errors_match_sequential() {
+    let xml = large_dict_xml().replacen(
+        r#"<lemma id="2002" rev="2002"><l t="сталь"><g v="NOUN"/>"#,
+        r#"<lemma id="2002" rev="2002"><l t="сталь"><g v="NUON"/>"#,
+        1,
+    );
+    let result = Dict::read_from_xml_parallel(xml.as_bytes(), &ParseOptions::default());
+    match result {
+        Err(Error::Located(e)) => assert_eq!(e.lemma_id, Some(2002)),
+        other => panic!("unexpected result: {:?}", other.map(|(d, _)| d.lemmata.len())),
+    }
+    assert_same_result(&xml, &ParseOptions::default());
+    assert_same_result(&xml, &lenient());
+
+    let truncated = &xml[..xml.len() / 2];
+    assert_same_result(truncated, &ParseOptions::default());
+    assert_same_result(truncated, &lenient());
+}
+
+#[test]
+fn test_parallel_with_markup_inside_comments() {
+    let xml = large_dict_xml().replacen(
+        r#"<lemma id="2001" "#,
+        "<!-- <lemma id=\"0\"> </lemmata> -->\n<lemma id=\"2001\" ",
+        1,
+    );
+    assert_same_result(&xml, &ParseOptions::default());
+    let (dict, _) = Dict::read_from_xml_parallel(xml.as_bytes(), &ParseOptions::default()).unwrap();
+    assert_eq!(dict.lemmata.len(), 7 * 401);
+}
+
+#[test]
+fn test_parallel_lenient_diagnostics() {
+    let xml = large_dict_xml()
+        .replacen(r#"<restr type="maybe""#, r#"<restr type="perhaps""#, 1)
+        .replacen(
+            r#"<lemma id="2002" rev="2002"><l t="сталь"><g v="NOUN"/>"#,
+            r#"<lemma id="2002" rev="2002"><l t="сталь"><g v="NUON"/>"#,
+            1,
+        )
+        .replacen(r#"to="5""#, r#"to="50""#, 1);
+    let options = lenient();
+    let (_, diagnostics) = Dict::read_from_xml_parallel(xml.as_bytes(), &options).unwrap();
+    assert_eq!(diagnostics.len(), 3);
+    assert_same_result(&xml, &options);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let mut options = lenient();
+    options.lemma_filter = Some(Arc::new(move |_: &Lemma| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        true
+    }));
+    Dict::read_from_xml_parallel(xml.as_bytes(), &options).unwrap();
+    // лексема с неизвестной граммемой пропускается до вызова условия отбора
+    assert_eq!(calls.load(Ordering::Relaxed), 7 * 401 - 1);
+}
+
+#[test]
+fn test_parallel_checks_tag_pairing() {
+    let xml = large_dict_xml().replacen(
+        r#"<link id="2" from="3" to="5" type="2"/>"#,
+        r#"<link id="2" from="3" to="5" type="2">"#,
+        1,
+    );
+    assert!(Dict::read_from_xml_with_options(xml.as_bytes(), &lenient()).is_err());
+    assert_same_result(&xml, &ParseOptions::default());
+    assert_same_result(&xml, &lenient());
+
+    // незакрытая лексема обнаруживается на закрывающем теге `lemmata`
+    let xml = large_dict_xml().replacen("</lemma>\n<lemma id=\"2006\"", "/lemma>\n<lemma id=\"2006\"", 1);
+    assert!(Dict::read_from_xml_with_options(xml.as_bytes(), &lenient()).is_err());
+    assert_same_result(&xml, &lenient());
+
+    // сообщения на строке закрывающего тега `lemmata` сохраняют свой столбец
+    let xml = large_dict_xml();
+    let end = xml.find("</lemmata>").unwrap();
+    let xml = format!("{}{}", &xml[..end], xml[end..].replace('\n', "").replacen(r#"to="5""#, r#"to="50""#, 1));
+    let (_, diagnostics) = Dict::read_from_xml_parallel(xml.as_bytes(), &lenient()).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_same_result(&xml, &lenient());
+}
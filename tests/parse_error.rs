@@ -0,0 +1,61 @@
+mod common;
+
+use std::fs;
+
+use opencorpora::error::Error;
+use opencorpora::{Dict, ParsingState};
+
+use common::DICT_PATH;
+
+/// возвращает словарь с заменённым фрагментом и позицию замены: (смещение, строка, столбец).
+fn broken_dict(from: &str, to: &str) -> (String, (usize, usize, usize)) {
+    let xml = fs::read_to_string(DICT_PATH).unwrap().replacen(from, to, 1);
+    let offset = xml.find(to).unwrap();
+    let before = &xml[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before[before.rfind('\n').map_or(0, |pos| pos + 1)..].chars().count() + 1;
+    (xml, (offset, line, column))
+}
+
+#[test]
+fn test_error_inside_lemma() {
+    let (xml, (offset, line, column)) = broken_dict(
+        r#"<f t="стали"><g v="sing"/><g v="gent"/>"#,
+        r#"<f t="стали"><g v="sing"/><g v="gnt"/>"#,
+    );
+    let e = match Dict::read_from_xml(xml.as_bytes()) {
+        Err(Error::Located(e)) => e,
+        r => panic!("unexpected result: {:?}", r),
+    };
+    // ошибка указывает на элемент <g v="gnt"/>
+    let g_offset = offset + r#"<f t="стали"><g v="sing"/>"#.len();
+    let g_column = column + r#"<f t="стали"><g v="sing"/>"#.chars().count();
+    assert_eq!((e.offset, e.line, e.column), (g_offset, line, g_column));
+    assert_eq!(e.state, ParsingState::LemmaF);
+    assert_eq!(e.lemma_id, Some(2));
+    assert!(matches!(e.source, Error::UnknownGrammeme(ref name) if name == "gnt"));
+    assert_eq!(
+        e.to_string(),
+        format!(
+            "unknown grammeme: 'gnt' at line {}, column {} (byte {}, state LemmaF, lemma 2)",
+            line, g_column, g_offset
+        )
+    );
+}
+
+#[test]
+fn test_error_in_link() {
+    let (xml, (offset, line, column)) = broken_dict(
+        r#"<link id="2" from="3" to="5" type="2"/>"#,
+        r#"<link id="2" from="3" to="50" type="2"/>"#,
+    );
+    let e = match Dict::read_from_xml(xml.as_bytes()) {
+        Err(Error::Located(e)) => e,
+        r => panic!("unexpected result: {:?}", r),
+    };
+    assert_eq!((e.offset, e.line, e.column), (offset, line, column));
+    assert_eq!(e.state, ParsingState::Links);
+    assert_eq!(e.lemma_id, None);
+    assert!(matches!(e.source, Error::UnknownLemma(50)));
+    assert_eq!(e.source.to_string(), "unknown lemma id: 50");
+}
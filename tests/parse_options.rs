@@ -0,0 +1,172 @@
+mod common;
+
+use std::fs;
+use std::io::BufReader;
+
+use opencorpora::error::Error;
+use opencorpora::{Dict, DictReader, ParseOptions, ParsingState};
+
+use common::{load_dict, DICT_PATH};
+
+fn lenient() -> ParseOptions {
+    let mut options = ParseOptions::default();
+    options.strict = false;
+    options
+}
+
+#[test]
+fn test_lenient_mode_skips_broken_records() {
+    let xml = fs::read_to_string(DICT_PATH)
+        .unwrap()
+        .replacen(r#"<restr type="forbidden""#, r#"<restr type="sometimes""#, 1)
+        .replacen(r#"<g v="sing"/><g v="gent"/></f><f t="стали">"#, r#"<g v="sing"/><g v="gnt"/></f><f t="стали">"#, 1)
+        .replacen(r#"from="3" to="5""#, r#"from="3" to="50""#, 1);
+    let expected = load_dict();
+
+    assert!(matches!(Dict::read_from_xml(xml.as_bytes()), Err(Error::Located(_))));
+
+    let (dict, diagnostics) = Dict::read_from_xml_with_options(xml.as_bytes(), &lenient()).unwrap();
+    assert_eq!(dict.restrictions.len(), expected.restrictions.len() - 1);
+    let ids: Vec<_> = dict.lemmata.iter().map(|l| l.id).collect();
+    assert_eq!(ids, [1, 3, 4, 5, 6, 7]);
+    assert_eq!(dict.lemmata[1], expected.lemmata[2]);
+    let ids: Vec<_> = dict.links.iter().map(|l| l.id).collect();
+    assert_eq!(ids, [1, 3]);
+    assert!(dict.lemma(2).is_none());
+
+    assert_eq!(diagnostics.len(), 3, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].state, ParsingState::Restriction);
+    assert_eq!(diagnostics[0].source.to_string(), "XML dictionary parsing error: invalid restriction kind: 'sometimes'");
+    assert_eq!(diagnostics[1].state, ParsingState::LemmaF);
+    assert_eq!(diagnostics[1].lemma_id, Some(2));
+    assert!(matches!(diagnostics[1].source, Error::UnknownGrammeme(ref name) if name == "gnt"));
+    assert_eq!(diagnostics[2].state, ParsingState::Links);
+    assert!(matches!(diagnostics[2].source, Error::UnknownLemma(50)));
+}
+
+#[test]
+fn test_unknown_link_type() {
+    let xml = fs::read_to_string(DICT_PATH)
+        .unwrap()
+        .replacen(r#"to="5" type="2""#, r#"to="5" type="9""#, 1);
+
+    match Dict::read_from_xml(xml.as_bytes()) {
+        Err(Error::Located(e)) => assert!(matches!(e.source, Error::UnknownLinkKind(9))),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let (dict, diagnostics) = Dict::read_from_xml_with_options(xml.as_bytes(), &lenient()).unwrap();
+    let ids: Vec<_> = dict.links.iter().map(|l| l.id).collect();
+    assert_eq!(ids, [1, 3]);
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].state, ParsingState::Links);
+    assert_eq!(diagnostics[0].source.to_string(), "unknown link type id: 9");
+}
+
+#[test]
+fn test_lenient_mode_on_valid_dict() {
+    let expected = load_dict();
+    let file = fs::File::open(DICT_PATH).unwrap();
+    let (dict, diagnostics) = Dict::read_from_xml_with_options(file, &lenient()).unwrap();
+    assert_eq!(dict, expected);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_lenient_mode_keeps_markup_errors_fatal() {
+    let xml = fs::read_to_string(DICT_PATH).unwrap().replacen("</f></lemma>", "</g></lemma>", 1);
+    assert!(Dict::read_from_xml_with_options(xml.as_bytes(), &lenient()).is_err());
+}
+
+#[test]
+fn test_lemmata_only() {
+    let expected = load_dict();
+    let mut options = ParseOptions::default();
+    options.grammemes = false;
+    options.restrictions = false;
+    options.link_types = false;
+    options.links = false;
+    let file = fs::File::open(DICT_PATH).unwrap();
+    let (dict, _) = Dict::read_from_xml_with_options(file, &options).unwrap();
+    // граммемы нужны для разбора лексем
+    assert_eq!(dict.grammemes, expected.grammemes);
+    assert!(dict.restrictions.is_empty());
+    assert_eq!(dict.lemmata, expected.lemmata);
+    assert!(dict.link_kinds.is_empty());
+    assert!(dict.links.is_empty());
+}
+
+#[test]
+fn test_links_only() {
+    let expected = load_dict();
+    // ошибки в пропущенных разделах не обнаруживаются
+    let xml = fs::read_to_string(DICT_PATH)
+        .unwrap()
+        .replacen(r#"<restr type="forbidden""#, r#"<restr type="sometimes""#, 1)
+        .replacen(r#"<g v="sing"/>"#, r#"<g v="singular"/>"#, 1);
+    let mut options = ParseOptions::default();
+    options.grammemes = false;
+    options.restrictions = false;
+    options.lemmata = false;
+    options.link_types = false;
+    let (dict, _) = Dict::read_from_xml_with_options(xml.as_bytes(), &options).unwrap();
+    assert!(dict.grammemes.is_empty());
+    assert!(dict.restrictions.is_empty());
+    assert!(dict.lemmata.is_empty());
+    // без лексем раздел связей пропускается, и типы связей для него не читаются
+    assert!(dict.link_kinds.is_empty());
+    assert!(dict.links.is_empty());
+    let mut binary = Vec::new();
+    dict.save_binary(&mut binary).unwrap();
+    assert_eq!(Dict::load_binary(binary.as_slice()).unwrap(), dict);
+    let mut xml = Vec::new();
+    dict.write_xml(&mut xml).unwrap();
+    assert_eq!(Dict::read_from_xml(xml.as_slice()).unwrap(), dict);
+
+    let file = BufReader::new(fs::File::open(DICT_PATH).unwrap());
+    let mut reader = DictReader::with_options(file, options).unwrap();
+    let links: Vec<_> = reader.links().map(|link| link.unwrap()).collect();
+    assert_eq!(links.len(), expected.links.len());
+    for (link, expected) in links.iter().zip(&expected.links) {
+        assert_eq!(link.id, expected.id);
+        assert_eq!(link.from, expected.from.id);
+        assert_eq!(link.to, expected.to.id);
+        assert_eq!(link.kind, expected.kind);
+    }
+}
+
+#[test]
+fn test_links_only_lenient() {
+    let xml = fs::read_to_string(DICT_PATH)
+        .unwrap()
+        .replacen(r#"to="5" type="2""#, r#"to="5" type="9""#, 1);
+    let mut options = lenient();
+    options.lemmata = false;
+    // пропущенные без разбора связи не проверяются
+    let (dict, diagnostics) = Dict::read_from_xml_with_options(xml.as_bytes(), &options).unwrap();
+    assert!(dict.links.is_empty());
+    assert!(!dict.link_kinds.is_empty());
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+    // читатель разбирает связи и без лексем
+    let mut reader = DictReader::with_options(xml.as_bytes(), options).unwrap();
+    assert_eq!(reader.links().filter_map(|link| link.ok()).count(), 2);
+    let diagnostics = reader.take_diagnostics();
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert!(matches!(diagnostics[0].source, Error::UnknownLinkKind(9)));
+}
+
+#[test]
+fn test_reader_skips_sections() {
+    let mut options = ParseOptions::default();
+    options.restrictions = false;
+    options.lemmata = false;
+    let file = BufReader::new(fs::File::open(DICT_PATH).unwrap());
+    let mut reader = DictReader::with_options(file, options).unwrap();
+    assert!(!reader.grammemes().unwrap().is_empty());
+    assert!(reader.restrictions().unwrap().is_empty());
+    assert_eq!(reader.lemmata().count(), 0);
+    assert!(!reader.link_kinds().unwrap().is_empty());
+    assert_eq!(reader.links().count(), 3);
+    reader.finish().unwrap();
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use opencorpora::Predictor;
+
+use common::load_dict;
+
+#[test]
+fn test_predict() {
+    let dict = load_dict();
+    let predictor = Predictor::new(&dict);
+
+    let predictions = predictor.predict("Гранатами");
+    assert_eq!(predictions.len(), 1);
+    assert_eq!(predictions[0].normal_form, "гранат");
+    assert_eq!(predictions[0].tag.to_string(), "NOUN,inan,masc plur,ablt");
+    assert!((predictions[0].score - 1.0).abs() < f64::EPSILON);
+
+    let predictions = predictor.predict("дали");
+    assert!(predictions.len() > 1);
+    assert!(predictions.windows(2).all(|w| w[0].score >= w[1].score));
+    let total: f64 = predictions.iter().map(|p| p.score).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+
+    // правила для окончания "ями" отрезают всё слово, поэтому используется более короткое окончание
+    let predictions = predictor.predict("ями");
+    assert!(!predictions.is_empty());
+    assert!(predictions.iter().all(|p| p.normal_form.starts_with("ям")));
+
+    assert!(predictor.predict("").is_empty());
+}
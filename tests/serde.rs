@@ -0,0 +1,89 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use std::sync::Arc;
+
+use opencorpora::error::Error;
+use opencorpora::{Dict, Grammeme, GrammemeSet, Lemma, Link};
+
+use common::load_dict;
+
+#[test]
+fn test_dict_json_round_trip() {
+    let dict = load_dict();
+    let json = serde_json::to_string(&dict).unwrap();
+    let copy: Dict = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(copy, dict);
+    assert!(Arc::ptr_eq(&copy.links[0].from, &copy.lemmata[2]));
+    assert!(Arc::ptr_eq(&copy.lemmata[0].grammemes[0], &copy.grammemes[1]));
+    assert_eq!(copy.lemmata[0].grammemes[0].index, 1);
+    assert_eq!(copy.lookup("стали").count(), dict.lookup("стали").count());
+}
+
+#[test]
+fn test_lemma_and_link_json() {
+    let dict = load_dict();
+
+    let value = serde_json::to_value(&*dict.lemmata[0]).unwrap();
+    assert_eq!(value["word"], "файл");
+    assert_eq!(value["grammemes"], serde_json::json!(["NOUN", "inan", "masc"]));
+    assert_eq!(value["forms"][1]["grammemes"], serde_json::json!(["sing", "gent"]));
+
+    let lemma: Lemma = serde_json::from_value(value).unwrap();
+    assert_eq!(lemma.forms.len(), dict.lemmata[0].forms.len());
+    assert_eq!(lemma.grammemes[2].name, "masc");
+    assert!(lemma.grammeme_set().is_empty());
+
+    let value = serde_json::to_value(&dict.links[1]).unwrap();
+    assert_eq!(value, serde_json::json!({ "id": 2, "from": 3, "to": 5, "kind": 2 }));
+    let link: Link = serde_json::from_value(value).unwrap();
+    assert_eq!((link.from.id, link.to.id, link.kind.id), (3, 5, 2));
+}
+
+#[test]
+fn test_grammeme_json_is_not_in_dict_sets() {
+    let dict = load_dict();
+    let plur = dict.grammeme("plur").unwrap();
+    let json = serde_json::to_string(&**plur).unwrap();
+    let grammeme: Grammeme = serde_json::from_str(&json).unwrap();
+    assert_eq!(grammeme.name, "plur");
+
+    // номер граммемы не сериализуется и не совпадает с номерами граммем словаря
+    let mut set = GrammemeSet::new();
+    assert!(!set.insert(&grammeme));
+    assert!(set.is_empty());
+    assert!(!dict.grammeme_set(["plur"]).unwrap().contains(&grammeme));
+    assert!(!dict.grammeme_set([dict.grammemes[0].name.as_str()]).unwrap().contains(&grammeme));
+}
+
+#[test]
+fn test_lemma_json_round_trip_and_resolve() {
+    let dict = load_dict();
+    let json = serde_json::to_string(&*dict.lemmata[0]).unwrap();
+    let mut lemma: Lemma = serde_json::from_str(&json).unwrap();
+
+    let wanted = dict.grammeme_set(["plur", "gent"]).unwrap();
+    // заглушки граммем не совпадают с граммемами словаря
+    assert!(lemma.inflect(&wanted).is_none());
+
+    dict.resolve(&mut lemma).unwrap();
+    assert_eq!(lemma, *dict.lemmata[0]);
+    assert!(Arc::ptr_eq(&lemma.grammemes[0], &dict.grammemes[1]));
+    assert_eq!(lemma.inflect(&wanted).unwrap().word, "файлов");
+
+    let mut value = serde_json::to_value(&*dict.lemmata[0]).unwrap();
+    value["forms"][0]["grammemes"][0] = serde_json::json!("nope");
+    let mut lemma: Lemma = serde_json::from_value(value).unwrap();
+    assert!(matches!(dict.resolve(&mut lemma), Err(Error::UnknownGrammeme(name)) if name == "nope"));
+}
+
+#[test]
+fn test_dict_json_dangling_reference() {
+    let dict = load_dict();
+    let mut value = serde_json::to_value(&dict).unwrap();
+    value["links"][0]["to"] = serde_json::json!(100);
+    let err = serde_json::from_value::<Dict>(value).unwrap_err();
+    assert!(err.to_string().contains("invalid lemma id: '100'"), "{}", err);
+}
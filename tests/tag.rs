@@ -0,0 +1,34 @@
+mod common;
+
+use opencorpora::error::Error;
+use opencorpora::{Tag, TagStyle};
+
+use common::load_dict;
+
+#[test]
+fn test_tag_parse_and_format() {
+    let dict = load_dict();
+
+    let tag = Tag::parse(&dict, "NOUN,inan,masc plur,gent").unwrap();
+    assert_eq!(tag.lemma.len(), 3);
+    assert_eq!(tag.form.len(), 2);
+    assert_eq!(tag.to_string(), "NOUN,inan,masc plur,gent");
+    assert_eq!(tag.format(TagStyle::Cyrillic), "СУЩ,неод,мр мн,рд");
+
+    let lemma = &dict.lemmata[0];
+    assert_eq!(lemma.tag(&lemma.forms[7]), tag);
+    assert_eq!(Tag::parse(&dict, "СУЩ,неод,мр мн,рд").unwrap(), tag);
+    assert_eq!(tag.grammeme_set(), dict.grammeme_set(["NOUN", "inan", "masc", "plur", "gent"]).unwrap());
+
+    let tag = Tag::parse(&dict, "INFN,perf").unwrap();
+    assert!(tag.form.is_empty());
+    assert_eq!(tag.to_string(), "INFN,perf");
+
+    match Tag::parse(&dict, "NOUN,inan,masc plur,gen") {
+        Err(Error::InvalidTag { grammeme, position, .. }) => {
+            assert_eq!(grammeme, "gen");
+            assert_eq!(position, 20);
+        }
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
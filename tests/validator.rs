@@ -0,0 +1,108 @@
+mod common;
+
+use std::sync::Arc;
+
+use opencorpora::{Form, Lemma, RestrictionScope, Validator, Violation, ViolationKind};
+
+use common::load_dict;
+
+#[test]
+fn test_validate_dict() {
+    let dict = load_dict();
+    assert_eq!(dict.validate().unwrap(), []);
+}
+
+#[test]
+fn test_validate_lemma() {
+    let dict = load_dict();
+    let validator = Validator::new(&dict).unwrap();
+    let grammeme = |name: &str| dict.grammemes.iter().find(|g| g.name == name).unwrap().clone();
+
+    // существительное без рода, с родом во множественном числе и с временем
+    let lemma = Lemma {
+        id: 100,
+        revision: 1,
+        word: "кот".to_owned(),
+        grammemes: vec![grammeme("NOUN"), grammeme("anim")],
+        forms: vec![
+            Form {
+                word: "кот".to_owned(),
+                grammemes: vec![grammeme("sing"), grammeme("nomn")],
+            },
+            Form {
+                word: "коты".to_owned(),
+                grammemes: vec![grammeme("plur"), grammeme("nomn"), grammeme("masc"), grammeme("past")],
+            },
+        ],
+    };
+
+    let violations = validator.validate_lemma(&lemma);
+    let gndr = dict
+        .restrictions
+        .iter()
+        .position(|r| r.right_grammeme.as_ref().map(|g| g.name.as_str()) == Some("GNdr"))
+        .unwrap();
+    let plur_gndr = dict.restrictions.len() - 1;
+
+    assert_eq!(violations.len(), 4, "{:?}", violations);
+    assert_eq!(violations[0].form_index, None);
+    assert_eq!(violations[0].kind, ViolationKind::Missing { restriction: gndr });
+    assert_eq!(violations[0].to_string(), format!("lemma 100: obligatory restriction #{} isn't satisfied", gndr));
+    assert_eq!(violations[1].form_index, Some(1));
+    assert_eq!(
+        violations[1].kind,
+        ViolationKind::Forbidden {
+            restriction: plur_gndr,
+            grammeme: grammeme("masc"),
+        }
+    );
+    for violation in &violations[2..] {
+        assert_eq!(violation.form_index, Some(1));
+    }
+    let not_allowed: Vec<Arc<_>> = violations[2..]
+        .iter()
+        .map(|v| match v.kind {
+            ViolationKind::NotAllowed { scope, ref grammeme } => {
+                assert_eq!(scope, RestrictionScope::Form);
+                grammeme.clone()
+            }
+            ref kind => panic!("unexpected violation: {:?}", kind),
+        })
+        .collect();
+    assert_eq!(not_allowed, [grammeme("masc"), grammeme("past")]);
+}
+
+#[test]
+fn test_validate_lemma_without_forms() {
+    let dict = load_dict();
+    let validator = Validator::new(&dict).unwrap();
+    let position = |right: &str| {
+        dict.restrictions
+            .iter()
+            .position(|r| {
+                r.left_grammeme.as_ref().map(|g| g.name.as_str()) == Some("NOUN")
+                    && r.right_grammeme.as_ref().map(|g| g.name.as_str()) == Some(right)
+            })
+            .unwrap()
+    };
+
+    let lemma = Lemma {
+        id: 100,
+        word: "кот".to_owned(),
+        grammemes: ["NOUN", "inan", "masc"].iter().map(|&name| dict.grammeme(name).unwrap().clone()).collect(),
+        ..Lemma::default()
+    };
+
+    let violations = validator.validate_lemma(&lemma);
+    let expected: Vec<_> = ["CAse", "NMbr"]
+        .iter()
+        .map(|&right| Violation {
+            lemma_id: 100,
+            form_index: None,
+            kind: ViolationKind::Missing {
+                restriction: position(right),
+            },
+        })
+        .collect();
+    assert_eq!(violations, expected);
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use std::fs;
+
+use opencorpora::Dict;
+
+use common::{load_dict, DICT_PATH};
+
+#[test]
+fn test_write_xml_round_trip() {
+    let dict = load_dict();
+    let mut buf = Vec::new();
+    dict.write_xml(&mut buf).unwrap();
+
+    // тестовый словарь записан в том же виде, что и словари opencorpora.org
+    assert_eq!(String::from_utf8(buf.clone()).unwrap(), fs::read_to_string(DICT_PATH).unwrap());
+
+    let copy = Dict::read_from_xml(&buf[..]).unwrap();
+    assert_eq!(copy, dict);
+}
+
+#[test]
+fn test_write_xml_escapes_text() {
+    let mut dict = load_dict();
+    let mut lemma = (*dict.lemmata[0]).clone();
+    lemma.word = "<\"файл\" & 'ко'>".to_owned();
+    lemma.forms[0].word = lemma.word.clone();
+    dict.lemmata[0] = lemma.into();
+    dict.version = "0.92 & more".to_owned();
+
+    let mut buf = Vec::new();
+    dict.write_xml(&mut buf).unwrap();
+    let copy = Dict::read_from_xml(&buf[..]).unwrap();
+    assert_eq!(copy.version, dict.version);
+    assert_eq!(copy.lemmata[0].word, dict.lemmata[0].word);
+    assert_eq!(copy.lemmata[0].forms[0].word, dict.lemmata[0].word);
+}
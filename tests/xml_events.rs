@@ -0,0 +1,50 @@
+mod common;
+
+use std::fs;
+
+use opencorpora::error::Error;
+use opencorpora::{Dict, ParseOptions};
+
+use common::{load_dict, DICT_PATH};
+
+#[test]
+fn test_comments_pi_and_doctype_are_ignored() {
+    let xml = fs::read_to_string(DICT_PATH)
+        .unwrap()
+        .replacen("?>\n", "?>\n<!DOCTYPE dictionary>\n<!-- opencorpora -->\n<?generator test?>\n", 1)
+        .replacen("<lemmata>\n", "<lemmata>\n<!-- лексемы -->\n", 1)
+        .replacen("</links>", "<!-- конец --></links>", 1);
+    let expected = load_dict();
+
+    let dict = Dict::read_from_xml(xml.as_bytes()).unwrap();
+    assert_eq!(dict, expected);
+}
+
+#[test]
+fn test_cdata_is_an_error() {
+    let xml = fs::read_to_string(DICT_PATH)
+        .unwrap()
+        .replacen("<lemmata>\n", "<lemmata>\n<![CDATA[лексемы]]>", 1);
+
+    match Dict::read_from_xml(xml.as_bytes()) {
+        Err(Error::Located(e)) => assert!(matches!(e.source, Error::UnexpectedEvent(_)), "{}", e),
+        other => panic!("unexpected result: {:?}", other.map(|d| d.lemmata.len())),
+    }
+}
+
+#[test]
+fn test_truncated_file_is_an_error() {
+    let xml = fs::read_to_string(DICT_PATH).unwrap();
+    let truncated = &xml.as_bytes()[..xml.find("<link_types>").unwrap()];
+
+    match Dict::read_from_xml(truncated) {
+        Err(Error::Located(e)) => assert!(matches!(e.source, Error::UnexpectedEof), "{}", e),
+        other => panic!("unexpected result: {:?}", other.map(|d| d.lemmata.len())),
+    }
+    let mut options = ParseOptions::default();
+    options.strict = false;
+    match Dict::read_from_xml_with_options(truncated, &options) {
+        Err(Error::Located(e)) => assert!(matches!(e.source, Error::UnexpectedEof), "{}", e),
+        other => panic!("unexpected result: {:?}", other.map(|(d, _)| d.lemmata.len())),
+    }
+}